@@ -1,5 +1,6 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand_chacha::rand_core::SeedableRng;
+use tabulate::column_planner::PlanOptions;
 use tabulate::table::{Table, WrapOptionsVarWidths};
 use tabulate::table_renderers::NullTableRenderer;
 use textwrap::{WordSeparator, WordSplitter};
@@ -34,6 +35,7 @@ fn worker(ncols: usize, transposed_table: &Table<String>) {
         transposed_table,
         &NullTableRenderer,
         &mut wrap_opts,
+        &PlanOptions::default(),
     )
     .unwrap();
 }
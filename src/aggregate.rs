@@ -0,0 +1,209 @@
+//! Aggregates of the numeric cells in a column, e.g. the sum, as shown in the
+//! footer row of a table.
+
+use crate::ansi;
+use crate::table::Table;
+
+/// An aggregate of the numeric cells in a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// The number of numeric cells.
+    Count,
+}
+
+impl Aggregate {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sum" => Some(Self::Sum),
+            "mean" => Some(Self::Mean),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "count" => Some(Self::Count),
+            _ => None,
+        }
+    }
+
+    /// Compute the aggregate of `numbers`, each along with the text it's
+    /// parsed from, formatted as a cell. The sum has as many decimal places
+    /// as the most precise number, and the mean two more, while the minimum
+    /// and the maximum are kept as their text. Return an empty string if the
+    /// aggregate is undefined, e.g. the mean of no number.
+    fn compute(&self, numbers: &[(f64, String)]) -> String {
+        let places = numbers
+            .iter()
+            .map(|(_, text)| decimal_places(text))
+            .max()
+            .unwrap_or(0);
+        let sum = numbers.iter().map(|(x, _)| x).sum::<f64>();
+        let text_of = |number: Option<&(f64, String)>| {
+            number.map_or_else(String::new, |(_, text)| text.clone())
+        };
+        match self {
+            Self::Sum => format!("{:.*}", places, sum),
+            Self::Mean if numbers.is_empty() => String::new(),
+            Self::Mean => {
+                format!("{:.*}", places + 2, sum / numbers.len() as f64)
+            }
+            Self::Min => {
+                text_of(numbers.iter().min_by(|a, b| a.0.total_cmp(&b.0)))
+            }
+            Self::Max => {
+                text_of(numbers.iter().max_by(|a, b| a.0.total_cmp(&b.0)))
+            }
+            Self::Count => numbers.len().to_string(),
+        }
+    }
+}
+
+/// Parse `cell` as a number, ignoring the surrounding whitespace and the
+/// colors. Return the number and its text, or `None` if `cell` is not a
+/// finite number.
+fn parse_number(cell: &str) -> Option<(f64, String)> {
+    let text = ansi::strip_sgr(cell).trim().to_owned();
+    // `inf`, `NaN` and the like are not taken as numbers.
+    if !text.contains(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let value: f64 = text.parse().ok()?;
+    value.is_finite().then_some((value, text))
+}
+
+/// The most decimal places of an aggregate, beyond which an `f64` has no
+/// precision left.
+const MAX_DECIMAL_PLACES: usize = 17;
+
+/// Return the number of decimal places of the number written as `text`, i.e.
+/// the number of digits after the decimal point less the exponent, if any,
+/// e.g. 3 for `1.5e-2`, up to [`MAX_DECIMAL_PLACES`].
+fn decimal_places(text: &str) -> usize {
+    let (mantissa, exponent) =
+        text.split_once(['e', 'E']).unwrap_or((text, "0"));
+    let fraction_digits = mantissa.split_once('.').map_or(0, |(_, fraction)| {
+        fraction.bytes().take_while(u8::is_ascii_digit).count()
+    });
+    // An exponent too long to parse moves the point beyond any place.
+    let exponent: i64 = exponent.parse().unwrap_or_else(|_| {
+        if exponent.starts_with('-') {
+            i64::MIN
+        } else {
+            i64::MAX
+        }
+    });
+    let places = i64::try_from(fraction_digits)
+        .unwrap_or(i64::MAX)
+        .saturating_sub(exponent);
+    places.clamp(0, MAX_DECIMAL_PLACES as i64) as usize
+}
+
+/// Compute the footer row of `table` given the aggregates of some columns
+/// `(col_idx, aggregate)`, where the other columns are left empty. Neither
+/// the header nor the footer row, if any, is aggregated, and the cells that
+/// are not numbers are skipped. Return the footer row, and the coordinates
+/// `(row_idx, col_idx)` of the skipped cells other than the empty ones.
+pub fn footer_row(
+    table: &Table<String>,
+    aggregates: &[(usize, Aggregate)],
+) -> (Vec<String>, Vec<(usize, usize)>) {
+    let mut row = vec![String::new(); table.ncols()];
+    let mut skipped = vec![];
+    let body_start = usize::from(table.has_header());
+    let body_end = table.nrows() - usize::from(table.has_footer());
+    for &(j, aggregate) in aggregates {
+        let mut numbers = vec![];
+        for i in body_start..body_end {
+            let cell = table.get(i, j).unwrap();
+            match parse_number(cell) {
+                Some(number) => numbers.push(number),
+                None if cell.trim().is_empty() => (),
+                None => skipped.push((i, j)),
+            }
+        }
+        row[j] = aggregate.compute(&numbers);
+    }
+    (row, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimal_places, footer_row, parse_number, Aggregate};
+    use crate::table::Table;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number(" 1.50 "), Some((1.5, "1.50".into())));
+        assert_eq!(
+            parse_number("\x1b[31m-2\x1b[0m"),
+            Some((-2.0, "-2".into()))
+        );
+        assert_eq!(parse_number("1e3"), Some((1000.0, "1e3".into())));
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_number("1e999"), None);
+        assert_eq!(parse_number("1,000"), None);
+        assert_eq!(parse_number(""), None);
+    }
+
+    #[test]
+    fn test_decimal_places() {
+        assert_eq!(decimal_places("12"), 0);
+        assert_eq!(decimal_places("1.25"), 2);
+        assert_eq!(decimal_places("1.5e3"), 0);
+        assert_eq!(decimal_places("1.25E1"), 1);
+        assert_eq!(decimal_places("1e-3"), 3);
+        assert_eq!(decimal_places("1.5e-2"), 3);
+        assert_eq!(decimal_places("1.5e+2"), 0);
+        assert_eq!(decimal_places("1.5e-17"), 17);
+        assert_eq!(decimal_places("1e-70000"), 17);
+        assert_eq!(decimal_places("1e-99999999999999999999"), 17);
+        assert_eq!(decimal_places("1.5e99999999999999999999"), 0);
+    }
+
+    #[test]
+    fn test_footer_row() {
+        let cells = [
+            "name", "qty", "price", //
+            "a", "3", "1.5", //
+            "b", "", "2.25", //
+            "c", "n/a", "0.75",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let mut table = Table::from_vec(cells, 4).unwrap();
+        table.set_header(true);
+        let aggregates = [(1, Aggregate::Sum), (2, Aggregate::Mean)];
+        let (row, skipped) = footer_row(&table, &aggregates);
+        assert_eq!(row, vec!["", "3", "1.5000"]);
+        assert_eq!(skipped, vec![(3, 1)]);
+
+        let aggregates = [
+            (0, Aggregate::Count),
+            (1, Aggregate::Mean),
+            (2, Aggregate::Max),
+        ];
+        let (row, _) = footer_row(&table, &aggregates);
+        assert_eq!(row, vec!["0", "3.00", "2.25"]);
+        table.set_footer_row(row);
+        let (row, _) = footer_row(&table, &[(2, Aggregate::Min)]);
+        assert_eq!(row, vec!["", "", "0.75"]);
+
+        let cells = ["1e-3", "1.5e-2", "2e-3", "1.5e-2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let table = Table::from_vec(cells, 2).unwrap();
+        let aggregates = [(0, Aggregate::Sum), (1, Aggregate::Sum)];
+        let (row, _) = footer_row(&table, &aggregates);
+        assert_eq!(row, vec!["0.003", "0.030"]);
+
+        let cells = ["1", "1e-70000"].into_iter().map(String::from).collect();
+        let table = Table::from_vec(cells, 1).unwrap();
+        let aggregates = [(0, Aggregate::Sum), (1, Aggregate::Sum)];
+        let (row, _) = footer_row(&table, &aggregates);
+        assert_eq!(row, vec!["1", "0.00000000000000000"]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Functions for ANSI SGR (Select Graphic Rendition) escape sequences, e.g.
+//! `\x1b[31m`, as found in colored text.
+//!
+//! The display width of these sequences is already taken as zero by
+//! [`textwrap::core::display_width`], and thus by the column planner.
+
+use std::borrow::Cow;
+
+/// The SGR sequence that resets all styles.
+pub const SGR_RESET: &str = "\x1b[0m";
+
+/// Find the first SGR sequence in `text`. Return its byte range and its
+/// parameters, e.g. `"1;31"` for `\x1b[1;31m`.
+fn find_sgr(text: &str) -> Option<(usize, usize, &str)> {
+    let mut offset = 0;
+    while let Some(i) = text[offset..].find("\x1b[") {
+        let start = offset + i;
+        let params_start = start + 2;
+        let params_len = text[params_start..]
+            .find(|c: char| !c.is_ascii_digit() && c != ';' && c != ':')
+            .unwrap_or(text.len() - params_start);
+        let params_end = params_start + params_len;
+        if text[params_end..].starts_with('m') {
+            return Some((
+                start,
+                params_end + 1,
+                &text[params_start..params_end],
+            ));
+        }
+        // Not an SGR sequence. Keep searching after it.
+        offset = params_start;
+    }
+    None
+}
+
+/// Remove all SGR sequences from `text`.
+pub fn strip_sgr(text: &str) -> Cow<'_, str> {
+    if find_sgr(text).is_none() {
+        return Cow::from(text);
+    }
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, end, _)) = find_sgr(rest) {
+        stripped.push_str(&rest[..start]);
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+    Cow::from(stripped)
+}
+
+/// Remove the leading whitespace of `text`, keeping the SGR sequences before
+/// and among it.
+pub fn trim_start(text: &str) -> Cow<'_, str> {
+    let mut sgrs = String::new();
+    let mut rest = text.trim_start();
+    while let Some((0, end, _)) = find_sgr(rest) {
+        sgrs.push_str(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    if sgrs.is_empty() {
+        Cow::from(rest)
+    } else {
+        Cow::from(sgrs + rest)
+    }
+}
+
+/// Get the longest prefix of `text` within display `width`, keeping the SGR
+/// sequences in it intact.
+pub fn truncate_to_width(text: &str, width: usize) -> &str {
+    let mut end = 0;
+    let mut prefix_width = 0;
+    while end < text.len() {
+        let rest = &text[end..];
+        if let Some((0, sgr_end, _)) = find_sgr(rest) {
+            end += sgr_end;
+            continue;
+        }
+        let c = rest.chars().next().unwrap();
+        let mut buf = [0; 4];
+        prefix_width += textwrap::core::display_width(c.encode_utf8(&mut buf));
+        if prefix_width > width {
+            break;
+        }
+        end += c.len_utf8();
+    }
+    &text[..end]
+}
+
+/// Make each line of wrapped text self-contained with respect to styles, by
+/// closing the styles still active at the end of a line with a reset, and
+/// reopening them at the beginning of the next line. This way the styles
+/// won't bleed into the table borders and padding.
+pub fn carry_styles_across_lines(lines: &mut [Cow<'_, str>]) {
+    // The SGR sequences in effect, from oldest to newest.
+    let mut active: Vec<String> = vec![];
+    for line in lines.iter_mut() {
+        if active.is_empty() && find_sgr(line).is_none() {
+            continue;
+        }
+        let reopened = active.concat();
+        let mut rest: &str = line;
+        while let Some((_, end, params)) = find_sgr(rest) {
+            if params.is_empty() || params == "0" {
+                active.clear();
+            } else {
+                if params.starts_with("0;") {
+                    active.clear();
+                }
+                active.push(format!("\x1b[{}m", params));
+            }
+            rest = &rest[end..];
+        }
+        if !reopened.is_empty() {
+            line.to_mut().insert_str(0, &reopened);
+        }
+        if !active.is_empty() {
+            line.to_mut().push_str(SGR_RESET);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_sgr() {
+        assert_eq!(strip_sgr("\x1b[1;31mfoo\x1b[0m bar"), "foo bar");
+        // Other CSI sequences are left as is.
+        assert_eq!(strip_sgr("\x1b[2Jfoo\x1b[m"), "\x1b[2Jfoo");
+        assert!(matches!(strip_sgr("foo"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_trim_start() {
+        assert_eq!(trim_start("  foo "), "foo ");
+        assert_eq!(trim_start("\x1b[31m  foo\x1b[0m"), "\x1b[31mfoo\x1b[0m");
+        assert_eq!(
+            trim_start(" \x1b[1m \x1b[31m foo"),
+            "\x1b[1m\x1b[31mfoo"
+        );
+        assert!(matches!(trim_start(" foo"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("abcdef", 4), "abcd");
+        assert_eq!(
+            truncate_to_width("\x1b[31mab\x1b[0mcd", 3),
+            "\x1b[31mab\x1b[0mc"
+        );
+        assert_eq!(truncate_to_width("中文字", 5), "中文");
+        assert_eq!(truncate_to_width("ab", 4), "ab");
+    }
+
+    #[test]
+    fn test_carry_styles_across_lines() {
+        let mut lines = vec![
+            Cow::from("\x1b[31mfoo"),
+            Cow::from("bar \x1b[1mbaz"),
+            Cow::from("qux\x1b[0m quux"),
+            Cow::from("corge"),
+        ];
+        carry_styles_across_lines(&mut lines);
+        assert_eq!(
+            lines,
+            vec![
+                Cow::from("\x1b[31mfoo\x1b[0m"),
+                Cow::from("\x1b[31mbar \x1b[1mbaz\x1b[0m"),
+                Cow::from("\x1b[31m\x1b[1mqux\x1b[0m quux"),
+                Cow::from("corge"),
+            ]
+        );
+    }
+}
@@ -1,11 +1,258 @@
 use crate::table::{Table, TableRenderer, WrapOptionsVarWidths};
 use crate::try_wrap;
+use std::collections::HashMap;
 
-/// Try wrap a column of strings. Return the display widths of the wrapped
-/// lines of each string.
-fn try_wrap_col(col: &[String], opts: &textwrap::Options) -> Vec<Vec<usize>> {
-    col.iter()
-        .map(|text| try_wrap::try_wrap(text, opts))
+/// The range of width within which the planner decides a column width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WidthRange {
+    pub min: usize,
+    /// `None` if unbounded.
+    pub max: Option<usize>,
+}
+
+/// The user-specified constraint on the width of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidthConstraint {
+    /// The width is fixed.
+    Fixed(usize),
+    /// The width is decided by the planner within the range.
+    Range(WidthRange),
+    /// The width is the percentage of the table width less the layout width.
+    Percent(usize),
+    /// The width is the number of shares of the width left by the other
+    /// columns, where all fractional columns share the width in proportion.
+    Fraction(usize),
+}
+
+impl WidthConstraint {
+    /// The width is decided by the planner without constraint.
+    pub const ANY: Self = Self::Range(WidthRange { min: 0, max: None });
+}
+
+/// `Some(width)` is a fixed width, and `None` is a width without constraint.
+impl From<Option<usize>> for WidthConstraint {
+    fn from(value: Option<usize>) -> Self {
+        match value {
+            Some(width) => Self::Fixed(width),
+            None => Self::ANY,
+        }
+    }
+}
+
+/// The objective that the planner minimizes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Objective {
+    /// The total number of lines taken by the rows.
+    #[default]
+    TotalLines,
+    /// The height of the tallest row.
+    MaxRowHeight,
+    /// The total number of lines taken by the rows, where the `i`-th row
+    /// counts `weights[i]` times, e.g. `vec![3]` to count the header row three
+    /// times. The rows beyond `weights` count once.
+    WeightedLines(Vec<usize>),
+    /// The area of the table, i.e. the total number of lines times the width
+    /// of the table. The table is narrowed to the width of the least area, so
+    /// it may not use up the total width.
+    Area,
+}
+
+impl Objective {
+    /// Get objective by name, i.e. "lines", "max-row-height", "area", or
+    /// "weighted:W1,W2,..." where `Wi` is the weight of the `i`-th row.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lines" => Some(Self::TotalLines),
+            "max-row-height" => Some(Self::MaxRowHeight),
+            "area" => Some(Self::Area),
+            _ => {
+                let weights = name.strip_prefix("weighted:")?;
+                let weights: Result<Vec<usize>, _> =
+                    weights.split(',').map(str::parse).collect();
+                weights.ok().map(Self::WeightedLines)
+            }
+        }
+    }
+
+    /// Evaluate the objective value of the number of lines of each row. The
+    /// truncated rows count one more line as a penalty of truncation, except
+    /// for the height of the tallest row, which is the height as rendered.
+    /// The area at a given table width is in proportion to the total number
+    /// of lines, which is thus its value.
+    fn eval(&self, nl: &NumWrappedLinesInColumn) -> usize {
+        if nl.is_inf() {
+            return NUM_WRAPPED_LINE_INF;
+        }
+        match self {
+            Self::TotalLines | Self::Area => nl.total(),
+            Self::MaxRowHeight => nl.nlines.iter().copied().max().unwrap_or(0),
+            // The weights are as large as the user likes, so the value
+            // saturates rather than overflows.
+            Self::WeightedLines(weights) => nl
+                .penalized()
+                .enumerate()
+                .map(|(i, x)| {
+                    x.saturating_mul(weights.get(i).copied().unwrap_or(1))
+                })
+                .fold(0, usize::saturating_add),
+        }
+    }
+}
+
+/// Which plan to prefer among the plans of the same objective value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Any of the plans.
+    #[default]
+    None,
+    /// The plan whose widths are the closest to each other.
+    Balanced,
+    /// The plan whose widths are in the same order as the longest lines of
+    /// the columns, such that a column of longer text is not narrower than a
+    /// column of shorter text.
+    Monotone,
+}
+
+impl TieBreak {
+    /// Get tie-break by name, i.e. "none", "balanced" or "monotone".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "balanced" => Some(Self::Balanced),
+            "monotone" => Some(Self::Monotone),
+            _ => None,
+        }
+    }
+
+    /// The cost of `widths` to minimize among the plans of the same objective
+    /// value, where `longest_widths[i]` is the width of the longest line of
+    /// the column whose width is `widths[i]`.
+    fn cost(&self, widths: &[usize], longest_widths: &[usize]) -> usize {
+        match self {
+            Self::None => 0,
+            // The sum of squares is the smallest when the widths are equal.
+            Self::Balanced => widths.iter().map(|x| x * x).sum(),
+            Self::Monotone => {
+                let mut cost = 0;
+                for (a, xa) in widths.iter().enumerate() {
+                    for (b, xb) in widths.iter().enumerate() {
+                        if longest_widths[a] < longest_widths[b] {
+                            cost += xa.saturating_sub(*xb);
+                        }
+                    }
+                }
+                cost
+            }
+        }
+    }
+}
+
+/// The minimum width of a column at which no wrapped line exceeds the width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinColumnWidth {
+    pub col_idx: usize,
+    pub width: usize,
+    pub cause: MinWidthCause,
+}
+
+/// What decides a [`MinColumnWidth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinWidthCause {
+    /// The cell at the row needs the width, e.g. the one with the longest
+    /// unbreakable word.
+    Row(usize),
+    /// The width is the minimum of the user's width range.
+    WidthRange,
+    /// No cell needs more than a single character, which is the least width
+    /// of any column.
+    Unconstrained,
+}
+
+/// How to size the columns whose widths are decided by the planner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sizing {
+    /// Use up the total width. This is the default, which keeps the widths
+    /// of the tables planned before the sizing was selectable.
+    #[default]
+    Fill,
+    /// Use the widths of the longest lines of the columns if they fit in the
+    /// total width. Otherwise, wrap the columns without using more width
+    /// than needed, as if shrinking the table without tolerance unless
+    /// specified otherwise.
+    Compact,
+}
+
+impl Sizing {
+    /// Get sizing by name, i.e. "fill" or "compact".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fill" => Some(Self::Fill),
+            "compact" => Some(Self::Compact),
+            _ => None,
+        }
+    }
+}
+
+/// What to do if some words are longer than their column widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Fail with an error.
+    Error,
+    /// Let the words overflow the columns whose widths are specified by user,
+    /// and fail if the planner can't decide the other widths.
+    #[default]
+    Overflow,
+    /// Break the words in the columns that can't fit them otherwise.
+    Break,
+}
+
+impl Fallback {
+    /// Get fallback by name, i.e. "error", "overflow" or "break".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(Self::Error),
+            "overflow" => Some(Self::Overflow),
+            "break" => Some(Self::Break),
+            _ => None,
+        }
+    }
+}
+
+/// Options of the planner.
+#[derive(Debug, Clone, Default)]
+pub struct PlanOptions {
+    pub objective: Objective,
+    pub tie_break: TieBreak,
+    pub sizing: Sizing,
+    pub fallback: Fallback,
+    /// If `Some(percent)`, shrink the table to the smallest total width at
+    /// which the objective value is at most `percent`% more than that at the
+    /// total width. `None` if the total width should be used up.
+    pub shrink_tolerance: Option<usize>,
+}
+
+/// Try wrap the `col_idx`-th column of a table at `width`. Return the display
+/// widths of the wrapped lines of each cell along the column. The header, if
+/// any, is wrapped by the options of the header.
+fn try_wrap_col(
+    col_idx: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+    width: usize,
+) -> Vec<Vec<usize>> {
+    let has_header = transposed_table.has_header();
+    transposed_table
+        .row(col_idx)
+        .unwrap()
+        .iter()
+        .enumerate()
+        .map(|(row_idx, text)| {
+            let is_header = row_idx == 0 && has_header;
+            try_wrap::try_wrap(
+                text,
+                opts.as_cell_width(is_header, col_idx, width),
+            )
+        })
         .collect()
 }
 
@@ -20,17 +267,20 @@ fn ensure_col_within_width(
 ) -> crate::Result<()> {
     for (row_idx, cell) in wrapped_col_widths.iter().enumerate() {
         if cell.iter().any(|line_width| line_width > &width) {
-            return Err(crate::Error::ColumnNotWideEnough(Some((
-                row_idx, col_idx,
-            ))));
+            return Err(crate::Error::ColumnNotWideEnough(row_idx, col_idx));
         }
     }
     Ok(())
 }
 
-/// Number of wrapped lines in each cell along a column.
-#[derive(Clone)]
-struct NumWrappedLinesInColumn(Vec<usize>);
+/// Number of wrapped lines in each cell along a column, or in each row when
+/// combined by [`max_with`](NumWrappedLinesInColumn::max_with).
+#[derive(Clone, PartialEq, Eq)]
+struct NumWrappedLinesInColumn {
+    nlines: Vec<usize>,
+    /// Whether each count in `nlines` is of a truncated cell.
+    truncated: Vec<bool>,
+}
 
 const NUM_WRAPPED_LINE_INF: usize = usize::MAX;
 
@@ -39,45 +289,85 @@ impl NumWrappedLinesInColumn {
     #[inline]
     fn inf(nrows: usize) -> Self {
         debug_assert!(nrows > 0);
-        Self(vec![NUM_WRAPPED_LINE_INF; nrows])
+        Self {
+            nlines: vec![NUM_WRAPPED_LINE_INF; nrows],
+            truncated: vec![false; nrows],
+        }
     }
 
     /// Construct a zero.
     #[inline]
     fn zero(nrows: usize) -> Self {
         debug_assert!(nrows > 0);
-        Self(vec![0; nrows])
+        Self {
+            nlines: vec![0; nrows],
+            truncated: vec![false; nrows],
+        }
     }
 
     /// Test if this instance is infinity.
     #[inline]
     fn is_inf(&self) -> bool {
-        self.0.iter().any(|x| *x == NUM_WRAPPED_LINE_INF)
+        self.nlines.contains(&NUM_WRAPPED_LINE_INF)
     }
 
     /// Construct from the display widths of the wrapped lines of each cell
     /// along a column.
     fn from_wrapped_col_widths(wrapped_col_widths: Vec<Vec<usize>>) -> Self {
         debug_assert!(!wrapped_col_widths.is_empty());
-        Self(wrapped_col_widths.iter().map(Vec::len).collect())
+        Self {
+            nlines: wrapped_col_widths.iter().map(Vec::len).collect(),
+            truncated: vec![false; wrapped_col_widths.len()],
+        }
     }
 
-    /// Compute element-wise max with another instance.
+    /// Compute element-wise max with another instance. Of the same number of
+    /// lines, a truncated cell counts as the larger.
     #[inline]
     fn max_with(&mut self, other: &NumWrappedLinesInColumn) {
-        debug_assert_eq!(self.0.len(), other.0.len());
-        for (x, y) in self.0.iter_mut().zip(other.0.iter()) {
-            *x = std::cmp::max(*x, *y);
+        debug_assert_eq!(self.nlines.len(), other.nlines.len());
+        for i in 0..self.nlines.len() {
+            let (x, y) = (self.nlines[i], other.nlines[i]);
+            if y > x {
+                self.nlines[i] = y;
+                self.truncated[i] = other.truncated[i];
+            } else if y == x {
+                self.truncated[i] |= other.truncated[i];
+            }
+        }
+    }
+
+    /// Count the cells with more than `max_lines` lines as truncated to
+    /// `max_lines`. This way, no more width is allocated to a column just to
+    /// shorten the cells that are truncated anyway, while widths that avoid
+    /// truncation are still preferred by the penalty in [`Objective::eval`].
+    #[inline]
+    fn truncate(&mut self, max_lines: usize) {
+        for (x, truncated) in self.nlines.iter_mut().zip(&mut self.truncated) {
+            if *x > max_lines {
+                *x = max_lines;
+                *truncated = true;
+            }
         }
     }
 
-    /// Compute the total number of wrapped lines.
+    /// The number of lines of each cell, plus one line as a penalty if the
+    /// cell is truncated.
+    #[inline]
+    fn penalized(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nlines
+            .iter()
+            .zip(&self.truncated)
+            .map(|(x, truncated)| x + usize::from(*truncated))
+    }
+
+    /// Compute the total number of wrapped lines, including the penalties.
     #[inline]
     fn total(&self) -> usize {
         if self.is_inf() {
             NUM_WRAPPED_LINE_INF
         } else {
-            self.0.iter().sum()
+            self.penalized().sum()
         }
     }
 }
@@ -87,7 +377,7 @@ impl std::fmt::Debug for NumWrappedLinesInColumn {
         if self.is_inf() {
             write!(f, "NumWrappedLinesInColumn(inf)")
         } else {
-            let v: Vec<_> = self.0.iter().map(|x| x.to_string()).collect();
+            let v: Vec<_> = self.penalized().map(|x| x.to_string()).collect();
             let v = v.join(", ");
             write!(f, "NumWrappedLinesInColumn([{}])", v)
         }
@@ -95,29 +385,54 @@ impl std::fmt::Debug for NumWrappedLinesInColumn {
 }
 
 /// Count the number of lines it takes to wrap all cells along the `col_idx`-th
-/// column of a table. If the column width is not specified by user explicitly,
-/// as indicated by `width_defined_by_user`, and if the wrapped lines don't fit
-/// within the width, then infinity will be returned.
+/// column of a table at `width`. If the column width is not specified by user
+/// explicitly, as indicated by `width_defined_by_user`, and if the wrapped
+/// lines don't fit within the width, then infinity will be returned. Cells
+/// longer than the maximum number of lines of the column are counted as
+/// truncated by [`NumWrappedLinesInColumn::truncate`].
 fn nlines_taken_by_column(
     col_idx: usize,
     transposed_table: &Table<String>,
-    opts: &textwrap::Options<'_>,
+    opts: &mut WrapOptionsVarWidths,
+    width: usize,
     width_defined_by_user: bool,
 ) -> NumWrappedLinesInColumn {
+    let max_lines = opts.max_lines_of(col_idx);
     let wrapped_col_widths =
-        try_wrap_col(transposed_table.row(col_idx).unwrap(), opts);
-    if width_defined_by_user {
+        try_wrap_col(col_idx, transposed_table, opts, width);
+    let mut nl = if width_defined_by_user {
         NumWrappedLinesInColumn::from_wrapped_col_widths(wrapped_col_widths)
     } else {
         let nrows = wrapped_col_widths.len();
-        match ensure_col_within_width(col_idx, &wrapped_col_widths, opts.width)
-        {
-            Err(_) => NumWrappedLinesInColumn::inf(nrows),
+        match ensure_col_within_width(col_idx, &wrapped_col_widths, width) {
+            Err(_) => return NumWrappedLinesInColumn::inf(nrows),
             Ok(()) => NumWrappedLinesInColumn::from_wrapped_col_widths(
                 wrapped_col_widths,
             ),
         }
+    };
+    if let Some(max_lines) = max_lines {
+        nl.truncate(max_lines);
     }
+    nl
+}
+
+/// Like [`nlines_taken_by_column`] for a column whose width is decided by the
+/// planner within `range`. A width below the range is infeasible, while a
+/// width above the range takes as many lines as the maximum width, since the
+/// column will be narrowed to the maximum width eventually.
+fn nlines_taken_by_ranged_column(
+    col_idx: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+    width: usize,
+    range: WidthRange,
+) -> NumWrappedLinesInColumn {
+    if width < range.min {
+        return NumWrappedLinesInColumn::inf(transposed_table.ncols());
+    }
+    let width = range.max.map_or(width, |max| width.min(max));
+    nlines_taken_by_column(col_idx, transposed_table, opts, width, false)
 }
 
 /// The width to allocate to column `n` at `dp(w, n)`.
@@ -136,17 +451,18 @@ impl Memo {
     /// Construct new [`Memo::Base`] from user-provided widths.
     fn from_user_widths(
         nrows: usize,
-        user_widths: &[Option<usize>],
+        user_widths: &[WidthConstraint],
         transposed_table: &Table<String>,
         opts: &mut WrapOptionsVarWidths,
     ) -> Self {
         let mut nl = NumWrappedLinesInColumn::zero(nrows);
         for (col_idx, uw) in user_widths.iter().enumerate() {
-            if let Some(uw) = uw {
+            if let WidthConstraint::Fixed(uw) = uw {
                 let cur_nl = nlines_taken_by_column(
                     col_idx,
                     transposed_table,
-                    opts.as_width(*uw),
+                    opts,
+                    *uw,
                     true,
                 );
                 nl.max_with(&cur_nl);
@@ -155,6 +471,15 @@ impl Memo {
         Self::Base(nl)
     }
 
+    /// Returns the cached memo. Panics if `self` is not `Cache`.
+    #[inline]
+    fn cached(&self) -> &[NumWrappedLinesInColumn] {
+        match self {
+            Memo::Base(_) => panic!("Memo not in Cache state"),
+            Memo::Cache(memo) => memo,
+        }
+    }
+
     /// Returns `true` if `self` is `Cache` and its last item is infinity.
     /// Panics if `self` is not `Cache`, or if the cached memo is empty.
     #[inline]
@@ -181,14 +506,15 @@ enum LbTightness {
 fn is_lb_tight(
     prev_dp: &NumWrappedLinesInColumn,
     nl: &NumWrappedLinesInColumn,
+    objective: &Objective,
 ) -> LbTightness {
     if prev_dp.is_inf() || nl.is_inf() {
         return LbTightness::Inf;
     }
-    let lb = std::cmp::max(prev_dp.total(), nl.total());
+    let lb = std::cmp::max(objective.eval(prev_dp), objective.eval(nl));
     let mut dp = prev_dp.clone();
     dp.max_with(nl);
-    let true_value = dp.total();
+    let true_value = objective.eval(&dp);
     if lb == true_value {
         LbTightness::Tight(dp)
     } else {
@@ -199,12 +525,15 @@ fn is_lb_tight(
 /// Compute `dp(w, n)`. Return `dp` and the optimal decision. Setup as a
 /// separate function to save some indentation. See [`dp`] for details and
 /// usage.
+#[allow(clippy::too_many_arguments)]
 fn dp_inductive_step_bisect(
     transposed_table: &Table<String>,
     opts: &mut WrapOptionsVarWidths,
     nrows: usize,
     w: usize,
     col_idx: usize,
+    range: WidthRange,
+    objective: &Objective,
     memo: &[NumWrappedLinesInColumn],
 ) -> (NumWrappedLinesInColumn, Decision) {
     // A cache of visited nl's.
@@ -214,32 +543,32 @@ fn dp_inductive_step_bisect(
     // objective as our objective. See documentation for details.
     let mut lo = 0;
     let mut hi = w;
-    // We will search for the width 1 <= i <= w such that `abs(prev_dp.total()
-    // - nl.total())` is the closest to 0 by first finding the largest width
-    // such that `prev_dp.total() - nl.total()` is the closest non-positive
-    // integer to 0, and then checking if there is any positive value closer
-    // to 0.
+    // We will search for the width 1 <= i <= w such that
+    // `abs(objective.eval(prev_dp) - objective.eval(nl))` is the closest to 0
+    // by first finding the largest width such that `objective.eval(prev_dp) -
+    // objective.eval(nl)` is the closest non-positive integer to 0, and then
+    // checking if there is any positive value closer to 0.
     while lo < hi {
-        let i = lo + (hi - lo + 1) / 2;
+        let i = lo + (hi - lo).div_ceil(2);
         let prev_dp = memo.get(w - i).unwrap();
         if prev_dp.is_inf() {
             hi = i - 1;
         } else {
-            let nl =
-                nls.get_mut(i)
-                    .unwrap()
-                    .get_or_insert(nlines_taken_by_column(
-                        col_idx,
-                        transposed_table,
-                        opts.as_width(i),
-                        false,
-                    ));
+            let nl = nls.get_mut(i).unwrap().get_or_insert(
+                nlines_taken_by_ranged_column(
+                    col_idx,
+                    transposed_table,
+                    opts,
+                    i,
+                    range,
+                ),
+            );
             if nl.is_inf() {
                 lo = i;
             } else {
-                // Instead of actually subtract `nl.total()` from
-                // `prev_dp.total()`, we make decision by comparing them.
-                if prev_dp.total() <= nl.total() {
+                // Instead of actually subtract the objective values, we make
+                // decision by comparing them.
+                if objective.eval(prev_dp) <= objective.eval(nl) {
                     lo = i;
                 } else {
                     hi = i - 1;
@@ -259,11 +588,12 @@ fn dp_inductive_step_bisect(
         // Split to avoid compiler error.
         let (nls, nls_plus1) = nls.split_at_mut(lo + 1);
         let nl = nls.last_mut().unwrap().get_or_insert_with(|| {
-            nlines_taken_by_column(
+            nlines_taken_by_ranged_column(
                 col_idx,
                 transposed_table,
-                opts.as_width(lo),
-                false,
+                opts,
+                lo,
+                range,
             )
         });
         if nl.is_inf() {
@@ -298,9 +628,14 @@ fn dp_inductive_step_bisect(
                     let nl_plus1 = nls_plus1.first().unwrap().as_ref().unwrap();
                     // If `nl` were infinity, `lo` would be `lo + 1`.
                     debug_assert!(!nl_plus1.is_inf());
-                    let lo_obj = std::cmp::max(prev_dp.total(), nl.total());
-                    let lo_plus1_obj =
-                        std::cmp::max(prev_dp_plus1.total(), nl_plus1.total());
+                    let lo_obj = std::cmp::max(
+                        objective.eval(prev_dp),
+                        objective.eval(nl),
+                    );
+                    let lo_plus1_obj = std::cmp::max(
+                        objective.eval(prev_dp_plus1),
+                        objective.eval(nl_plus1),
+                    );
                     if lo_obj <= lo_plus1_obj {
                         lo
                     } else {
@@ -321,29 +656,30 @@ fn dp_inductive_step_bisect(
     // We must have visited `approximate_opt_width`.
     let prev_dp = memo.get(w - approximate_opt_width).unwrap();
     let nl = nls.get(approximate_opt_width).unwrap().as_ref().unwrap();
-    match is_lb_tight(prev_dp, nl) {
+    match is_lb_tight(prev_dp, nl, objective) {
         LbTightness::Inf => panic!(), // This should not happen.
         LbTightness::Tight(dp) => (dp, approximate_opt_width),
         LbTightness::NotTight(dp) => {
-            let mut min_value = dp.total();
+            let mut min_value = objective.eval(&dp);
             let mut opt_dp = dp;
             let mut opt_width = approximate_opt_width;
             // Return true if tightness is reached.
             let mut line_search = |i: usize| -> bool {
                 let prev_dp = memo.get(w - i).unwrap();
                 let nl = nls.get_mut(i).unwrap().get_or_insert_with(|| {
-                    nlines_taken_by_column(
+                    nlines_taken_by_ranged_column(
                         col_idx,
                         transposed_table,
-                        opts.as_width(i),
-                        false,
+                        opts,
+                        i,
+                        range,
                     )
                 });
-                match is_lb_tight(prev_dp, nl) {
+                match is_lb_tight(prev_dp, nl, objective) {
                     // If lower bound is infinity, then it's tight.
                     LbTightness::Inf => true,
                     LbTightness::Tight(dp) => {
-                        let value = dp.total();
+                        let value = objective.eval(&dp);
                         if value < min_value {
                             min_value = value;
                             opt_dp = dp;
@@ -352,7 +688,7 @@ fn dp_inductive_step_bisect(
                         true
                     }
                     LbTightness::NotTight(dp) => {
-                        let value = dp.total();
+                        let value = objective.eval(&dp);
                         if value < min_value {
                             min_value = value;
                             opt_dp = dp;
@@ -379,12 +715,15 @@ fn dp_inductive_step_bisect(
 }
 
 #[cfg(any(test, feature = "bench-brute"))]
+#[allow(clippy::too_many_arguments)]
 fn dp_inductive_step_brute(
     transposed_table: &Table<String>,
     opts: &mut WrapOptionsVarWidths,
     nrows: usize,
     w: usize,
     col_idx: usize,
+    range: WidthRange,
+    objective: &Objective,
     memo: &[NumWrappedLinesInColumn],
 ) -> (NumWrappedLinesInColumn, Decision) {
     assert!(w < memo.len());
@@ -397,17 +736,18 @@ fn dp_inductive_step_brute(
             if prev_dp.is_inf() {
                 (NumWrappedLinesInColumn::inf(nrows), i)
             } else {
-                let mut nl = nlines_taken_by_column(
+                let mut nl = nlines_taken_by_ranged_column(
                     col_idx,
                     transposed_table,
-                    opts.as_width(i),
-                    false,
+                    opts,
+                    i,
+                    range,
                 );
                 nl.max_with(prev_dp);
                 (nl, i)
             }
         })
-        .min_by_key(|(nl, _)| nl.total())
+        .min_by_key(|(nl, _)| objective.eval(nl))
         .unwrap()
 }
 
@@ -424,15 +764,20 @@ fn dp_inductive_step_brute(
 /// - `opts`: the wrapping options.
 /// - `nrows`: the `ncols` of `transposed_table`.
 /// - `col_idx`: the column index of the `n`-th undecided column of the table.
+/// - `range`: the width range of the `n`-th undecided column.
+/// - `objective`: the objective to minimize.
 /// - `memo`: cached computed `dp(w, n-1)`, or `Base` if n == 0.
 /// - `out_memo`: to which to push `dp(w, n)` value.
 /// - `out_decisions`: to which to push the decision at `n`.
+#[allow(clippy::too_many_arguments)]
 fn dp(
     transposed_table: &Table<String>,
     opts: &mut WrapOptionsVarWidths,
     nrows: usize,
     w: usize,
     col_idx: usize,
+    range: WidthRange,
+    objective: &Objective,
     memo: &Memo,
     out_memo: &mut Vec<NumWrappedLinesInColumn>,
     out_decisions: &mut Vec<Decision>,
@@ -449,11 +794,12 @@ fn dp(
                 if base_memo.is_inf() {
                     (NumWrappedLinesInColumn::inf(nrows), w)
                 } else {
-                    let mut nl = nlines_taken_by_column(
+                    let mut nl = nlines_taken_by_ranged_column(
                         col_idx,
                         transposed_table,
-                        opts.as_width(w),
-                        false,
+                        opts,
+                        w,
+                        range,
                     );
                     nl.max_with(base_memo);
                     (nl, w)
@@ -466,6 +812,8 @@ fn dp(
                 nrows,
                 w,
                 col_idx,
+                range,
+                objective,
                 memo,
             ),
             #[cfg(feature = "bench-brute")]
@@ -475,6 +823,8 @@ fn dp(
                 nrows,
                 w,
                 col_idx,
+                range,
+                objective,
                 memo,
             ),
             #[cfg(all(test, not(feature = "bench-brute")))]
@@ -485,6 +835,8 @@ fn dp(
                     nrows,
                     w,
                     col_idx,
+                    range,
+                    objective,
                     memo,
                 );
                 let (dp_brute, _) = dp_inductive_step_brute(
@@ -493,9 +845,11 @@ fn dp(
                     nrows,
                     w,
                     col_idx,
+                    range,
+                    objective,
                     memo,
                 );
-                assert_eq!(dp.total(), dp_brute.total());
+                assert_eq!(objective.eval(&dp), objective.eval(&dp_brute));
                 (dp, decision)
             }
         }
@@ -504,14 +858,206 @@ fn dp(
     out_decisions.push(decision);
 }
 
+/// Check that every bounded width range in `user_widths` admits a width at
+/// which no wrapped line exceeds the width.
+fn check_width_ranges(
+    user_widths: &[WidthConstraint],
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+) -> crate::Result<()> {
+    for (col_idx, uw) in user_widths.iter().enumerate() {
+        if let WidthConstraint::Range(WidthRange { max: Some(max), .. }) = uw {
+            let min_width = min_col_width(col_idx, transposed_table, opts);
+            if min_width > *max {
+                return Err(crate::Error::WidthRangeInfeasible(
+                    col_idx, min_width,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the percentage and fractional widths in `user_widths` into fixed
+/// widths, where `usable_width` is the table width less the layout width.
+/// Percentages are of `usable_width`. Fractions share the width left by the
+/// fixed and percentage widths, and by the minimum widths of the columns to be
+/// decided by the planner, each of which takes a share of `1fr` as well. The
+/// shares of those columns are left to the planner.
+fn resolve_relative_widths(
+    user_widths: &mut [WidthConstraint],
+    user_total_width: usize,
+    table_layout_width: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+) -> crate::Result<()> {
+    if !user_widths.iter().any(|uw| {
+        matches!(
+            uw,
+            WidthConstraint::Percent(_) | WidthConstraint::Fraction(_)
+        )
+    }) {
+        return Ok(());
+    }
+    let not_large_enough =
+        || crate::Error::TotalWidthNotLargeEnough(user_total_width);
+    let usable_width = user_total_width
+        .checked_sub(table_layout_width)
+        .ok_or_else(not_large_enough)?;
+    // The sums saturate, since a sum of widths too large is not large
+    // enough anyway, and the fractions of a saturated sum still share the
+    // width roughly in proportion.
+    let mut sum_width: usize = 0;
+    let mut sum_fractions: usize = 0;
+    for (col_idx, uw) in user_widths.iter_mut().enumerate() {
+        match uw {
+            WidthConstraint::Fixed(width) => {
+                sum_width = sum_width.saturating_add(*width)
+            }
+            WidthConstraint::Range(range) => {
+                sum_width = sum_width.saturating_add(
+                    min_col_width(col_idx, transposed_table, opts)
+                        .max(range.min),
+                );
+                sum_fractions = sum_fractions.saturating_add(1);
+            }
+            WidthConstraint::Percent(percent) => {
+                let width = mul_div(usable_width, *percent, 100);
+                *uw = WidthConstraint::Fixed(width);
+                sum_width = sum_width.saturating_add(width);
+            }
+            WidthConstraint::Fraction(fraction) => {
+                sum_fractions = sum_fractions.saturating_add(*fraction)
+            }
+        }
+    }
+    let rest_width = usable_width
+        .checked_sub(sum_width)
+        .ok_or_else(not_large_enough)?;
+    // Round the cumulative shares so that the widths sum up to `rest_width`.
+    let mut cum_fractions: usize = 0;
+    let mut cum_width = 0;
+    for uw in user_widths.iter_mut() {
+        let fraction = match uw {
+            WidthConstraint::Fraction(fraction) => *fraction,
+            WidthConstraint::Range(_) => 1,
+            _ => continue,
+        };
+        cum_fractions =
+            sum_fractions.min(cum_fractions.saturating_add(fraction));
+        let width = if sum_fractions == 0 {
+            0
+        } else {
+            mul_div(rest_width, cum_fractions, sum_fractions) - cum_width
+        };
+        if let WidthConstraint::Fraction(_) = uw {
+            *uw = WidthConstraint::Fixed(width);
+        }
+        cum_width += width;
+    }
+    Ok(())
+}
+
+/// Compute `x * numerator / denominator` without overflow, where `numerator`
+/// is at most `denominator`, or the result is otherwise within `usize`.
+fn mul_div(x: usize, numerator: usize, denominator: usize) -> usize {
+    (x as u128 * numerator as u128 / denominator as u128) as usize
+}
+
 /// Automatically decide unfilled user-provided widths `user_widths` using
-/// dynamic programming.
-pub fn complete_user_widths(
-    mut user_widths: Vec<Option<usize>>,
+/// dynamic programming, minimizing the objective in `plan_opts`. Each element
+/// of `user_widths` converts into a [`WidthConstraint`], e.g. `Some(width)`
+/// for a fixed width and `None` for a width without constraint.
+///
+/// If the fallback in `plan_opts` is [`Fallback::Break`], long words are
+/// broken in the columns that can't fit them otherwise, which are recorded in
+/// `opts`. The columns whose widths, or maximum widths, are specified by user
+/// are broken if they are too narrow for their words. The other columns are broken one at a
+/// time, the widest first, until the widths can be decided.
+pub fn complete_user_widths<W: Into<WidthConstraint>>(
+    user_widths: Vec<W>,
+    user_total_width: Option<usize>,
+    transposed_table: &Table<String>,
+    table_renderer: &dyn TableRenderer,
+    opts: &mut WrapOptionsVarWidths<'_>,
+    plan_opts: &PlanOptions,
+) -> crate::Result<Vec<usize>> {
+    let user_widths: Vec<WidthConstraint> =
+        user_widths.into_iter().map(Into::into).collect();
+    if plan_opts.fallback != Fallback::Break {
+        return complete_constrained_widths(
+            user_widths,
+            user_total_width,
+            transposed_table,
+            table_renderer,
+            opts,
+            plan_opts,
+        );
+    }
+    for (col_idx, uw) in user_widths.iter().enumerate() {
+        if let WidthConstraint::Fixed(width) = uw {
+            let nl = nlines_taken_by_column(
+                col_idx,
+                transposed_table,
+                opts,
+                *width,
+                false,
+            );
+            if nl.is_inf() {
+                opts.set_break_words(col_idx);
+            }
+        }
+    }
+    loop {
+        match complete_constrained_widths(
+            user_widths.clone(),
+            user_total_width,
+            transposed_table,
+            table_renderer,
+            opts,
+            plan_opts,
+        ) {
+            Err(crate::Error::TableNotWideEnough(
+                min_total_width,
+                min_widths,
+            )) => {
+                // The widest column whose words are not broken yet.
+                let blocking = min_widths
+                    .iter()
+                    .filter(|mw| {
+                        matches!(mw.cause, MinWidthCause::Row(_))
+                            && !opts.break_words_of(mw.col_idx)
+                    })
+                    .max_by_key(|mw| mw.width);
+                match blocking {
+                    None => {
+                        return Err(crate::Error::TableNotWideEnough(
+                            min_total_width,
+                            min_widths,
+                        ))
+                    }
+                    Some(mw) => opts.set_break_words(mw.col_idx),
+                }
+            }
+            // The maximum width of the column is too narrow for its words.
+            Err(crate::Error::WidthRangeInfeasible(col_idx, _))
+                if !opts.break_words_of(col_idx) =>
+            {
+                opts.set_break_words(col_idx)
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Like [`complete_user_widths`], but without the fallback.
+fn complete_constrained_widths(
+    mut user_widths: Vec<WidthConstraint>,
     user_total_width: Option<usize>,
     transposed_table: &Table<String>,
     table_renderer: &dyn TableRenderer,
     opts: &mut WrapOptionsVarWidths<'_>,
+    plan_opts: &PlanOptions,
 ) -> crate::Result<Vec<usize>> {
     // The nrows of a transposed table is ncols.
     let ncols = transposed_table.nrows();
@@ -524,29 +1070,69 @@ pub fn complete_user_widths(
             ncols
         );
     }
-    // Indices of columns whose widths are not specified by user.
-    let undecided_cols: Vec<usize> = user_widths
+    let user_total_width = user_total_width.unwrap_or_else(textwrap::termwidth);
+    let table_layout_width = table_renderer.layout_width(ncols);
+    resolve_relative_widths(
+        &mut user_widths,
+        user_total_width,
+        table_layout_width,
+        transposed_table,
+        opts,
+    )?;
+    // Indices of columns whose widths are not specified by user, and their
+    // width ranges.
+    let (undecided_cols, ranges): (Vec<usize>, Vec<WidthRange>) = user_widths
         .iter()
         .enumerate()
-        .filter_map(|(j, uw)| if uw.is_none() { Some(j) } else { None })
+        .filter_map(|(j, uw)| match uw {
+            WidthConstraint::Range(range) => Some((j, *range)),
+            _ => None,
+        })
+        .unzip();
+    let mut completed_user_widths: Vec<usize> = user_widths
+        .iter()
+        .map(|uw| match uw {
+            WidthConstraint::Fixed(width) => *width,
+            _ => 0,
+        })
         .collect();
     if undecided_cols.is_empty() {
         // All user widths are filled, so user total width will be ignored.
-        let widths: Vec<_> = user_widths.into_iter().flatten().collect();
-        return Ok(widths);
+        return Ok(completed_user_widths);
     }
+    check_width_ranges(&user_widths, transposed_table, opts)?;
     let undecided_ncols = undecided_cols.len();
-    let user_total_width =
-        user_total_width.unwrap_or_else(|| textwrap::termwidth());
-    // Sum of user-specified widths.
-    let sum_decided_width: usize =
-        user_widths.iter().filter_map(|x| x.as_ref()).sum();
-    let table_layout_width = table_renderer.layout_width(ncols);
-    if user_total_width < sum_decided_width + table_layout_width {
+    // Sum of user-specified widths, including the minimums of width ranges.
+    let sum_decided_width: usize = completed_user_widths.iter().sum();
+    let sum_min_width: usize = ranges.iter().map(|range| range.min).sum();
+    if user_total_width < sum_decided_width + sum_min_width + table_layout_width
+    {
         return Err(crate::Error::TotalWidthNotLargeEnough(user_total_width));
     }
     // Total optimizable width.
     let sum_widths = user_total_width - sum_decided_width - table_layout_width;
+    if plan_opts.sizing == Sizing::Compact {
+        // The columns need not be wrapped if their longest lines fit.
+        let natural_widths: Vec<usize> = undecided_cols
+            .iter()
+            .zip(&ranges)
+            .map(|(col_idx, range)| {
+                longest_line_width(*col_idx, transposed_table)
+                    .max(range.min)
+                    .max(1)
+            })
+            .collect();
+        let fits = natural_widths.iter().sum::<usize>() <= sum_widths
+            && natural_widths.iter().zip(&ranges).all(|(width, range)| {
+                range.max.is_none_or(|max| *width <= max)
+            });
+        if fits {
+            for (col_idx, width) in undecided_cols.iter().zip(natural_widths) {
+                completed_user_widths[*col_idx] = width;
+            }
+            return Ok(completed_user_widths);
+        }
+    }
 
     // memo[w + n * (sum_widths + 1)] == dp(w, n).
     // However, we actually only need 2*(sum_widths+1) space for memo, since
@@ -566,13 +1152,15 @@ pub fn complete_user_widths(
             nrows,
             w,
             *undecided_cols.first().unwrap(),
+            ranges[0],
+            &plan_opts.objective,
             &memo,
             &mut new_memo,
             &mut decisions,
         );
     }
     memo = Memo::Cache(new_memo);
-    for col_idx in undecided_cols.iter().skip(1) {
+    for (col_idx, range) in undecided_cols.iter().zip(&ranges).skip(1) {
         let mut new_memo = Vec::with_capacity(sum_widths + 1);
         for w in 0..=sum_widths {
             dp(
@@ -581,6 +1169,8 @@ pub fn complete_user_widths(
                 nrows,
                 w,
                 *col_idx,
+                *range,
+                &plan_opts.objective,
                 &memo,
                 &mut new_memo,
                 &mut decisions,
@@ -590,87 +1180,670 @@ pub fn complete_user_widths(
     }
 
     if memo.is_last_inf() {
-        return Err(crate::Error::ColumnNotWideEnough(None));
+        let min_widths: Vec<MinColumnWidth> = undecided_cols
+            .iter()
+            .zip(&ranges)
+            .map(|(col_idx, range)| {
+                let min_width = min_col_width(*col_idx, transposed_table, opts);
+                if min_width < range.min {
+                    return MinColumnWidth {
+                        col_idx: *col_idx,
+                        width: range.min,
+                        cause: MinWidthCause::WidthRange,
+                    };
+                }
+                let cause = match widest_row(
+                    *col_idx,
+                    transposed_table,
+                    opts,
+                    min_width,
+                ) {
+                    Some(row_idx) => MinWidthCause::Row(row_idx),
+                    None => MinWidthCause::Unconstrained,
+                };
+                MinColumnWidth {
+                    col_idx: *col_idx,
+                    width: min_width,
+                    cause,
+                }
+            })
+            .collect();
+        let min_total_width = sum_decided_width
+            + min_widths.iter().map(|mw| mw.width).sum::<usize>()
+            + table_layout_width;
+        return Err(crate::Error::TableNotWideEnough(
+            min_total_width,
+            min_widths,
+        ));
     }
     let decisions = Table::from_vec(decisions, undecided_ncols).unwrap();
-    let mut widths = Vec::with_capacity(user_widths.len());
     let mut w = sum_widths;
-    for n in (0..undecided_ncols).rev() {
-        let decision = decisions.get(n, w).copied().unwrap();
-        widths.push(decision);
-        w -= decision;
+    let shrink_tolerance = match plan_opts.sizing {
+        Sizing::Fill => plan_opts.shrink_tolerance,
+        Sizing::Compact => Some(plan_opts.shrink_tolerance.unwrap_or(0)),
+    };
+    if plan_opts.objective == Objective::Area {
+        // Since `dp(w, n)` is known for every `w`, the `w` of the least area
+        // is found by a scan, the narrowest of the same area.
+        let memo = memo.cached();
+        w = (0..=sum_widths)
+            .filter(|w| !memo[*w].is_inf())
+            .min_by_key(|w| {
+                let table_width = sum_decided_width + w + table_layout_width;
+                memo[*w].total().saturating_mul(table_width)
+            })
+            .unwrap();
+    } else if let Some(tolerance) = shrink_tolerance {
+        // Since `dp(w, n)` is known for every `w`, the smallest `w` within
+        // the tolerance is found by a scan.
+        let memo = memo.cached();
+        let objective = &plan_opts.objective;
+        let max_value = objective.eval(memo.last().unwrap());
+        let max_value = max_value
+            .saturating_add(max_value.saturating_mul(tolerance) / 100);
+        w = (0..=sum_widths)
+            .find(|w| objective.eval(&memo[*w]) <= max_value)
+            .unwrap();
     }
-    widths.reverse();
-
-    let mut widths_iter = widths.into_iter();
-    for uw in user_widths.iter_mut() {
-        if uw.is_none() {
-            uw.get_or_insert(widths_iter.next().unwrap());
-        }
+    let mut w_left = w;
+    for n in (0..undecided_ncols).rev() {
+        let decision = decisions.get(n, w_left).copied().unwrap();
+        // A width beyond the maximum takes as many lines as the maximum, so
+        // the column is narrowed to the maximum, leaving the rest unused.
+        completed_user_widths[undecided_cols[n]] =
+            ranges[n].max.map_or(decision, |max| decision.min(max));
+        w_left -= decision;
     }
-    let completed_user_widths = user_widths.into_iter().flatten().collect();
+    refine_widths(
+        &mut completed_user_widths,
+        &undecided_cols,
+        &ranges,
+        w,
+        transposed_table,
+        opts,
+        &plan_opts.objective,
+    );
+    break_ties(
+        &mut completed_user_widths,
+        &undecided_cols,
+        &ranges,
+        transposed_table,
+        opts,
+        plan_opts,
+    );
     Ok(completed_user_widths)
 }
 
-#[cfg(test)]
-mod complete_user_widths_tests {
-    use const_format::concatcp;
-    use proptest::prelude::*;
-
-    use super::complete_user_widths;
-    use crate::table::{Table, TableRenderer};
-    use crate::table_renderers::NullTableRenderer;
+/// The most cells that [`refine_widths`] visits, i.e. the number of partial
+/// plans visited times the number of rows, after which the best plan found so
+/// far is taken.
+const MAX_REFINE_CELLS: usize = 10_000_000;
 
-    use super::{
-        ensure_col_within_width, try_wrap_col, NumWrappedLinesInColumn,
-        WrapOptionsVarWidths,
+/// Search by branch and bound for widths of `undecided_cols` that sum up to
+/// at most `sum_widths` and are better than `widths` in objective value.
+///
+/// The DP keeps only the best [`NumWrappedLinesInColumn`] of each total
+/// width, which need not be the best to combine with the columns after it, so
+/// its plan is a good bound to start with but not always optimal. Only the
+/// widths at which a column takes different lines than at the width before
+/// are tried, and the width left is given to the columns in order up to their
+/// maximum widths.
+#[allow(clippy::too_many_arguments)]
+fn refine_widths(
+    widths: &mut [usize],
+    undecided_cols: &[usize],
+    ranges: &[WidthRange],
+    sum_widths: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+    objective: &Objective,
+) {
+    let base_nl = nlines_taken_by_decided_cols(
+        widths,
+        undecided_cols,
+        transposed_table,
+        opts,
+    );
+    let mut nl = base_nl.clone();
+    let mut candidates = Vec::with_capacity(undecided_cols.len());
+    for (col_idx, range) in undecided_cols.iter().zip(ranges) {
+        nl.max_with(&nlines_taken_by_ranged_column(
+            *col_idx,
+            transposed_table,
+            opts,
+            widths[*col_idx],
+            *range,
+        ));
+        // Beyond the longest line, no line is wrapped.
+        let natural_width = longest_line_width(*col_idx, transposed_table)
+            .max(range.min)
+            .max(1);
+        let mut col_candidates: Vec<(usize, NumWrappedLinesInColumn)> =
+            vec![];
+        for width in 1..=sum_widths.min(natural_width) {
+            let cur_nl = nlines_taken_by_ranged_column(
+                *col_idx,
+                transposed_table,
+                opts,
+                width,
+                *range,
+            );
+            if !cur_nl.is_inf()
+                && col_candidates.last().is_none_or(|(_, nl)| *nl != cur_nl)
+            {
+                col_candidates.push((width, cur_nl));
+            }
+        }
+        if col_candidates.is_empty() {
+            return;
+        }
+        candidates.push(col_candidates);
+    }
+    let mut search = BranchAndBound {
+        candidates: &candidates,
+        min_widths: (0..=candidates.len())
+            .map(|n| candidates[n..].iter().map(|c| c[0].0).sum())
+            .collect(),
+        sum_widths,
+        objective,
+        best_value: objective.eval(&nl),
+        best_widths: None,
+        widths: Vec::with_capacity(candidates.len()),
+        cells_left: MAX_REFINE_CELLS,
     };
+    search.search(0, 0, &base_nl);
+    if let Some(best_widths) = search.best_widths {
+        let mut width_left = sum_widths - best_widths.iter().sum::<usize>();
+        for (n, mut width) in best_widths.into_iter().enumerate() {
+            let extra_width = ranges[n].max.map_or(width_left, |max| {
+                width_left.min(max.saturating_sub(width))
+            });
+            width += extra_width;
+            width_left -= extra_width;
+            widths[undecided_cols[n]] = width;
+        }
+    }
+}
 
-    /// Max `ncols` of the table.
-    const MAX_NCOLS: usize = 5;
-    /// Max len of ASCII words.
-    const MAX_WORD_LEN: usize = 7;
-    /// Min number of words per cell.
-    const MIN_NUM_WORD: usize = 1;
-    /// Max number of words per cell.
-    const MAX_NUM_WORD: usize = 100;
-    /// Max degree-of-freedom of the width of each column.
-    const MAX_WIDTH_DOF: usize = 15;
-    /// `nrows` of the table.
-    const NROWS: usize = 3;
+/// The state of the search of [`refine_widths`].
+struct BranchAndBound<'a> {
+    /// The widths worth trying of each undecided column in ascending order,
+    /// with the lines taken at the widths.
+    candidates: &'a [Vec<(usize, NumWrappedLinesInColumn)>],
+    /// `min_widths[n]` is the sum of the smallest widths of the `n`-th and
+    /// later undecided columns.
+    min_widths: Vec<usize>,
+    sum_widths: usize,
+    objective: &'a Objective,
+    best_value: usize,
+    /// `None` until a plan better than the initial one is found.
+    best_widths: Option<Vec<usize>>,
+    /// The widths of the undecided columns before the current one.
+    widths: Vec<usize>,
+    cells_left: usize,
+}
 
-    /// Count number of lines taken by the table, and ensure that all columns
-    /// are within `widths`.
-    fn count_nlines_total(
-        transposed_table: &Table<String>,
-        opts: &mut WrapOptionsVarWidths<'_>,
-        widths: &[usize],
-    ) -> Result<usize, ()> {
-        let ncols = transposed_table.nrows();
-        assert_eq!(ncols, widths.len());
-        let mut nl_total =
-            NumWrappedLinesInColumn::zero(transposed_table.ncols());
-        for (col_idx, width) in widths.iter().enumerate() {
-            let col = transposed_table.row(col_idx).unwrap();
-            let wrapped_col_widths = try_wrap_col(col, opts.as_width(*width));
-            ensure_col_within_width(col_idx, &wrapped_col_widths, *width)
-                .map_err(|_| ())?;
-            let nl = NumWrappedLinesInColumn::from_wrapped_col_widths(
-                wrapped_col_widths,
-            );
-            nl_total.max_with(&nl);
+impl BranchAndBound<'_> {
+    /// Search the widths of the `n`-th and later undecided columns, where the
+    /// columns before take `used_width` and `nl` lines.
+    fn search(
+        &mut self,
+        n: usize,
+        used_width: usize,
+        nl: &NumWrappedLinesInColumn,
+    ) {
+        let Some(cells_left) = self.cells_left.checked_sub(nl.nlines.len())
+        else {
+            return;
+        };
+        self.cells_left = cells_left;
+        if n == self.candidates.len() {
+            let value = self.objective.eval(nl);
+            if value < self.best_value {
+                self.best_value = value;
+                self.best_widths = Some(self.widths.clone());
+            }
+            return;
+        }
+        // Each column takes no more lines than at the widest width it may
+        // take, which bounds the objective value from below.
+        let mut bound_nl = nl.clone();
+        for m in n..self.candidates.len() {
+            let max_width = self.max_width(m, used_width, n);
+            bound_nl.max_with(self.widest_candidate(m, max_width));
+        }
+        if self.objective.eval(&bound_nl) >= self.best_value {
+            return;
+        }
+        let max_width = self.max_width(n, used_width, n);
+        for (width, cur_nl) in self.candidates[n].iter().rev() {
+            if *width > max_width {
+                continue;
+            }
+            let mut next_nl = nl.clone();
+            next_nl.max_with(cur_nl);
+            self.widths.push(*width);
+            self.search(n + 1, used_width + width, &next_nl);
+            self.widths.pop();
         }
-        Ok(nl_total.total())
     }
 
-    /// Generate wrapping cases. When `infeasibility` is zero, the problems are
-    /// guaranteed feasible. The larger `infeasibility` is, the more likely the
-    /// problems are drawn infeasible.
-    fn generate_wrapping(
+    /// The widest width of the `m`-th undecided column, given that the
+    /// columns before the `n`-th take `used_width`, and the other columns
+    /// from the `n`-th on take their smallest widths.
+    fn max_width(&self, m: usize, used_width: usize, n: usize) -> usize {
+        self.sum_widths + self.candidates[m][0].0
+            - used_width
+            - self.min_widths[n]
+    }
+
+    /// The lines taken by the widest candidate of the `m`-th undecided
+    /// column within `max_width`.
+    fn widest_candidate(
+        &self,
+        m: usize,
+        max_width: usize,
+    ) -> &NumWrappedLinesInColumn {
+        let candidates = &self.candidates[m];
+        let i = candidates.partition_point(|(width, _)| *width <= max_width);
+        &candidates[i.max(1) - 1].1
+    }
+}
+
+/// The [`NumWrappedLinesInColumn`] of the columns of a table at `widths`,
+/// except for `undecided_cols`.
+fn nlines_taken_by_decided_cols(
+    widths: &[usize],
+    undecided_cols: &[usize],
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+) -> NumWrappedLinesInColumn {
+    let nrows = transposed_table.ncols();
+    let mut nl = NumWrappedLinesInColumn::zero(nrows);
+    for (col_idx, width) in widths.iter().enumerate() {
+        if !undecided_cols.contains(&col_idx) {
+            let cur_nl = nlines_taken_by_column(
+                col_idx,
+                transposed_table,
+                opts,
+                *width,
+                true,
+            );
+            nl.max_with(&cur_nl);
+        }
+    }
+    nl
+}
+
+/// Move width between two of `undecided_cols` at a time, as long as the move
+/// is preferred by the tie-break and doesn't worsen the objective value.
+fn break_ties(
+    widths: &mut [usize],
+    undecided_cols: &[usize],
+    ranges: &[WidthRange],
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+    plan_opts: &PlanOptions,
+) {
+    if plan_opts.tie_break == TieBreak::None || undecided_cols.len() < 2 {
+        return;
+    }
+    let base_nl = nlines_taken_by_decided_cols(
+        widths,
+        undecided_cols,
+        transposed_table,
+        opts,
+    );
+    // A cache of visited nl's of each undecided column, keyed by width.
+    let mut nls: Vec<HashMap<usize, NumWrappedLinesInColumn>> =
+        vec![HashMap::new(); undecided_cols.len()];
+    let mut eval = |widths: &[usize]| -> usize {
+        let mut nl = base_nl.clone();
+        for (n, col_idx) in undecided_cols.iter().enumerate() {
+            let width = widths[*col_idx];
+            let cur_nl = nls[n].entry(width).or_insert_with(|| {
+                nlines_taken_by_ranged_column(
+                    *col_idx,
+                    transposed_table,
+                    opts,
+                    width,
+                    ranges[n],
+                )
+            });
+            nl.max_with(cur_nl);
+        }
+        plan_opts.objective.eval(&nl)
+    };
+    let longest_widths: Vec<usize> = undecided_cols
+        .iter()
+        .map(|col_idx| longest_line_width(*col_idx, transposed_table))
+        .collect();
+    let cost = |widths: &[usize]| -> usize {
+        let undecided_widths: Vec<usize> = undecided_cols
+            .iter()
+            .map(|col_idx| widths[*col_idx])
+            .collect();
+        plan_opts.tie_break.cost(&undecided_widths, &longest_widths)
+    };
+    let mut value = eval(widths);
+    let mut cur_cost = cost(widths);
+    loop {
+        let mut moved = false;
+        for (a, ja) in undecided_cols.iter().enumerate() {
+            for (b, jb) in undecided_cols.iter().enumerate() {
+                if a == b {
+                    continue;
+                }
+                let max_step = widths[*ja]
+                    .saturating_sub(ranges[a].min.max(1))
+                    .min(ranges[b].max.map_or(usize::MAX, |max| {
+                        max.saturating_sub(widths[*jb])
+                    }));
+                // The widths of the same objective value need not be next to
+                // each other, so the best of the moves of any step is taken.
+                let mut best = None;
+                for step in 1..=max_step {
+                    widths[*ja] -= step;
+                    widths[*jb] += step;
+                    let new_cost = cost(widths);
+                    if new_cost < best.map_or(cur_cost, |(_, cost, _)| cost) {
+                        let new_value = eval(widths);
+                        if new_value <= value {
+                            best = Some((step, new_cost, new_value));
+                        }
+                    }
+                    widths[*ja] += step;
+                    widths[*jb] -= step;
+                }
+                if let Some((step, new_cost, new_value)) = best {
+                    widths[*ja] -= step;
+                    widths[*jb] += step;
+                    value = new_value;
+                    cur_cost = new_cost;
+                    moved = true;
+                }
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+}
+
+/// The display width of the longest line in the `col_idx`-th column of a
+/// table.
+fn longest_line_width(
+    col_idx: usize,
+    transposed_table: &Table<String>,
+) -> usize {
+    transposed_table
+        .row(col_idx)
+        .unwrap()
+        .iter()
+        .flat_map(|text| text.lines())
+        .map(textwrap::core::display_width)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The minimum width of the `col_idx`-th column of a table at which no
+/// wrapped line exceeds the width, e.g. the width of the longest word if words
+/// are not broken.
+fn min_col_width(
+    col_idx: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+) -> usize {
+    // At the width of the longest line, no line needs to be wrapped.
+    let mut hi = longest_line_width(col_idx, transposed_table).max(1);
+    let mut lo = 1;
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let nl =
+            nlines_taken_by_column(col_idx, transposed_table, opts, mid, false);
+        if nl.is_inf() {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    hi
+}
+
+/// The row of the first cell in the `col_idx`-th column of a table that needs
+/// at least `min_width` to wrap within the width. `None` if `min_width` is at
+/// most 1, which every column needs anyway.
+fn widest_row(
+    col_idx: usize,
+    transposed_table: &Table<String>,
+    opts: &mut WrapOptionsVarWidths,
+    min_width: usize,
+) -> Option<usize> {
+    if min_width <= 1 {
+        return None;
+    }
+    let width = min_width - 1;
+    let wrapped_col_widths =
+        try_wrap_col(col_idx, transposed_table, opts, width);
+    wrapped_col_widths
+        .iter()
+        .position(|cell| cell.iter().any(|line_width| *line_width > width))
+}
+
+/// Evaluate the objective value of wrapping a table at `widths`. Used in tests
+/// only.
+#[cfg(test)]
+fn eval_widths(
+    transposed_table: &Table<String>,
+    widths: &[usize],
+    opts: &mut WrapOptionsVarWidths,
+    objective: &Objective,
+) -> usize {
+    let nrows = transposed_table.ncols();
+    let mut nl = NumWrappedLinesInColumn::zero(nrows);
+    for (col_idx, w) in widths.iter().enumerate() {
+        let cur_nl =
+            nlines_taken_by_column(col_idx, transposed_table, opts, *w, true);
+        nl.max_with(&cur_nl);
+    }
+    objective.eval(&nl)
+}
+
+/// A page of columns to be rendered as a table of its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnPage {
+    /// The indices of the columns on the page, key columns first.
+    pub cols: Vec<usize>,
+    /// The widths of the columns on the page.
+    pub widths: Vec<usize>,
+}
+
+/// Split the columns of a table into consecutive pages that each fit in the
+/// total width, repeating the `key_cols` at the beginning of every page. The
+/// pages are filled from the left, each with as many columns as their minimum
+/// widths allow, and the widths on each page are then decided by
+/// [`complete_user_widths`].
+pub fn plan_column_pages<W: Into<WidthConstraint>>(
+    user_widths: Vec<W>,
+    user_total_width: Option<usize>,
+    key_cols: &[usize],
+    transposed_table: &Table<String>,
+    table_renderer: &dyn TableRenderer,
+    opts: &mut WrapOptionsVarWidths<'_>,
+    plan_opts: &PlanOptions,
+) -> crate::Result<Vec<ColumnPage>> {
+    let user_widths: Vec<WidthConstraint> =
+        user_widths.into_iter().map(Into::into).collect();
+    let ncols = transposed_table.nrows();
+    if user_widths.len() != ncols {
+        panic!(
+            "len(WIDTH_LIST) ({}) != table ncols ({})",
+            user_widths.len(),
+            ncols
+        );
+    }
+    check_width_ranges(&user_widths, transposed_table, opts)?;
+    let user_total_width = user_total_width.unwrap_or_else(textwrap::termwidth);
+    // Plan a page. Return the page and the columns whose words are broken.
+    let plan_page = |cols: Vec<usize>,
+                     opts: &WrapOptionsVarWidths<'_>|
+     -> crate::Result<(ColumnPage, Vec<usize>)> {
+        let page_table = transposed_table.select_rows(&cols);
+        let page_user_widths: Vec<_> =
+            cols.iter().map(|j| user_widths[*j]).collect();
+        let mut page_opts = opts.select_columns(&cols);
+        let widths = complete_user_widths(
+            page_user_widths,
+            Some(user_total_width),
+            &page_table,
+            table_renderer,
+            &mut page_opts,
+            plan_opts,
+        )?;
+        let broken_cols = cols
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| page_opts.break_words_of(*k))
+            .map(|(_, j)| *j)
+            .collect();
+        Ok((ColumnPage { cols, widths }, broken_cols))
+    };
+    let other_cols: Vec<usize> =
+        (0..ncols).filter(|j| !key_cols.contains(j)).collect();
+    if other_cols.is_empty() {
+        let (page, broken_cols) = plan_page(key_cols.to_vec(), opts)?;
+        for j in broken_cols {
+            opts.set_break_words(j);
+        }
+        return Ok(vec![page]);
+    }
+    let min_widths: Vec<usize> = user_widths
+        .iter()
+        .enumerate()
+        .map(|(j, uw)| match uw {
+            WidthConstraint::Fixed(width) => *width,
+            WidthConstraint::Range(range) => {
+                min_col_width(j, transposed_table, opts).max(range.min)
+            }
+            // Relative widths are resolved against each page.
+            WidthConstraint::Percent(_) | WidthConstraint::Fraction(_) => 0,
+        })
+        .collect();
+    let key_min_width: usize = key_cols.iter().map(|j| min_widths[*j]).sum();
+    // The width taken by the key columns and the `a..b`-th non-key columns at
+    // the least.
+    let page_min_width = |a: usize, b: usize| {
+        let min_width: usize = key_min_width
+            + other_cols[a..b].iter().map(|j| min_widths[*j]).sum::<usize>();
+        min_width + table_renderer.layout_width(key_cols.len() + b - a)
+    };
+
+    let n = other_cols.len();
+    let mut pages = vec![];
+    let mut a = 0;
+    while a < n {
+        // A page takes at least one non-key column, which can't be split
+        // further.
+        let mut b = a + 1;
+        while b < n && page_min_width(a, b + 1) <= user_total_width {
+            b += 1;
+        }
+        // The minimum widths may still be too narrow, e.g. for the relative
+        // widths, in which case the page is shrunk.
+        loop {
+            let mut cols = key_cols.to_vec();
+            cols.extend_from_slice(&other_cols[a..b]);
+            match plan_page(cols, opts) {
+                Ok((page, broken_cols)) => {
+                    for j in broken_cols {
+                        opts.set_break_words(j);
+                    }
+                    pages.push(page);
+                    break;
+                }
+                Err(_) if b > a + 1 => b -= 1,
+                Err(_) => {
+                    return Err(crate::Error::TotalWidthNotLargeEnough(
+                        user_total_width,
+                    ))
+                }
+            }
+        }
+        a = b;
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod complete_user_widths_tests {
+    use const_format::concatcp;
+    use proptest::prelude::*;
+
+    use super::{
+        complete_user_widths, min_col_width, plan_column_pages, Fallback,
+        MinColumnWidth, MinWidthCause, Objective, PlanOptions, Sizing,
+        TieBreak, WidthConstraint, WidthRange,
+    };
+    use crate::table::{Table, TableRenderer};
+    use crate::table_renderers::NullTableRenderer;
+
+    use super::{
+        ensure_col_within_width, eval_widths, longest_line_width,
+        nlines_taken_by_column, try_wrap_col, NumWrappedLinesInColumn,
+        WrapOptionsVarWidths,
+    };
+    use std::collections::HashMap;
+
+    /// Max `ncols` of the table.
+    const MAX_NCOLS: usize = 5;
+    /// Max `ncols` of the table to plan by exhaustive search.
+    const MAX_NCOLS_EXHAUSTIVE: usize = 3;
+    /// Max len of ASCII words.
+    const MAX_WORD_LEN: usize = 7;
+    /// Min number of words per cell.
+    const MIN_NUM_WORD: usize = 1;
+    /// Max number of words per cell.
+    const MAX_NUM_WORD: usize = 100;
+    /// Max degree-of-freedom of the width of each column.
+    const MAX_WIDTH_DOF: usize = 15;
+    /// `nrows` of the table.
+    const NROWS: usize = 3;
+
+    /// Evaluate the `objective` of the table wrapped at `widths`, and ensure
+    /// that all columns are within `widths`.
+    fn eval_objective(
+        transposed_table: &Table<String>,
+        opts: &mut WrapOptionsVarWidths<'_>,
+        widths: &[usize],
+        objective: &Objective,
+    ) -> Result<usize, ()> {
+        let ncols = transposed_table.nrows();
+        assert_eq!(ncols, widths.len());
+        let mut nl_total =
+            NumWrappedLinesInColumn::zero(transposed_table.ncols());
+        for (col_idx, width) in widths.iter().enumerate() {
+            let wrapped_col_widths =
+                try_wrap_col(col_idx, transposed_table, opts, *width);
+            ensure_col_within_width(col_idx, &wrapped_col_widths, *width)
+                .map_err(|_| ())?;
+            let nl = NumWrappedLinesInColumn::from_wrapped_col_widths(
+                wrapped_col_widths,
+            );
+            nl_total.max_with(&nl);
+        }
+        Ok(objective.eval(&nl_total))
+    }
+
+    /// Generate wrapping cases of at most `max_ncols` columns. When
+    /// `infeasibility` is zero, the problems are guaranteed feasible. The
+    /// larger `infeasibility` is, the more likely the problems are drawn
+    /// infeasible.
+    fn generate_wrapping(
+        max_ncols: usize,
         infeasibility: usize,
     ) -> impl Strategy<Value = (usize, Vec<usize>, Vec<Option<usize>>, Table<String>)>
     {
-        (1..=MAX_NCOLS)
+        (1..=max_ncols)
             .prop_flat_map(|ncols| {
                 (
                     // Table ncols.
@@ -705,7 +1878,7 @@ mod complete_user_widths_tests {
                             .collect();
                         let user_widths: Vec<Option<usize>> = widths
                             .iter()
-                            .zip(user_defined.into_iter())
+                            .zip(user_defined)
                             .map(
                                 |(w, ud)| if ud == 0 { Some(*w) } else { None },
                             )
@@ -736,37 +1909,120 @@ mod complete_user_widths_tests {
             })
     }
 
+    /// Generate the objectives to plan for.
+    fn generate_objective() -> impl Strategy<Value = Objective> {
+        prop_oneof![
+            Just(Objective::TotalLines),
+            Just(Objective::MaxRowHeight),
+            prop::collection::vec(0..4usize, 0..=NROWS)
+                .prop_map(Objective::WeightedLines),
+        ]
+    }
+
+    /// Generate the tie-breaks to plan with.
+    fn generate_tie_break() -> impl Strategy<Value = TieBreak> {
+        prop_oneof![
+            Just(TieBreak::None),
+            Just(TieBreak::Balanced),
+            Just(TieBreak::Monotone),
+        ]
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(1000))]
         #[test]
-        fn test_feasible_cases(case in generate_wrapping(0)) {
+        fn test_feasible_cases(
+            case in generate_wrapping(MAX_NCOLS, 0),
+            objective in generate_objective(),
+        ) {
             let (total_width, widths, user_widths, transposed_table) = case;
             instantiated_case(
                 total_width,
                 widths,
                 user_widths,
                 transposed_table,
+                &PlanOptions {
+                    objective,
+                    ..Default::default()
+                },
             );
         }
     }
 
+    /// A case where the best plan of each total width in the DP is not the
+    /// best to combine with the next column.
+    #[test]
+    fn test_feasible_case_dp_not_optimal() {
+        let cells = [
+            "a",
+            "a",
+            "a",
+            "aaa aaaaa aa aaaaa aaaaaaa a",
+            "a",
+            "aa a aaa aaaa aaa",
+            "a",
+            "aa aaaaa aa aaaaa aa aaaaaaa a",
+            "a aa aa aaaa aaa aaaa aaaa aaaa aaaa",
+        ];
+        let cells = cells.into_iter().map(String::from).collect();
+        let mut transposed_table = Table::from_vec(cells, NROWS).unwrap();
+        transposed_table.transpose();
+        instantiated_case(
+            23,
+            vec![9, 7, 7],
+            vec![None; 3],
+            transposed_table,
+            &PlanOptions::default(),
+        );
+    }
+
     fn generate_wrapping_infeasible(
     ) -> impl Strategy<Value = (usize, Vec<usize>, Vec<Option<usize>>, Table<String>)>
     {
-        (1..=MAX_WORD_LEN + MAX_NCOLS * MAX_WIDTH_DOF)
-            .prop_flat_map(generate_wrapping)
+        (1..=MAX_WORD_LEN + MAX_NCOLS * MAX_WIDTH_DOF).prop_flat_map(
+            |infeasibility| generate_wrapping(MAX_NCOLS, infeasibility),
+        )
     }
 
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(1000))]
         #[test]
-        fn test_infeasible_cases(case in generate_wrapping_infeasible()) {
+        fn test_infeasible_cases(
+            case in generate_wrapping_infeasible(),
+            objective in generate_objective(),
+        ) {
             let (total_width, widths, user_widths, transposed_table) = case;
             instantiated_case(
                 total_width,
                 widths,
                 user_widths,
                 transposed_table,
+                &PlanOptions {
+                    objective,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+        #[test]
+        fn test_tie_break_cases(
+            case in generate_wrapping(MAX_NCOLS_EXHAUSTIVE, 0),
+            objective in generate_objective(),
+            tie_break in generate_tie_break(),
+        ) {
+            let (total_width, _, user_widths, transposed_table) = case;
+            exhaustive_case(
+                total_width,
+                user_widths,
+                transposed_table,
+                &PlanOptions {
+                    objective,
+                    tie_break,
+                    ..Default::default()
+                },
             );
         }
     }
@@ -779,15 +2035,36 @@ mod complete_user_widths_tests {
             .break_words(false)
     }
 
-    /// Count number of lines taken by the table if widths are optimized, and
-    /// ensure that all columns are within the optimized `widths`.
-    fn count_nlines_total_for_user_widths(
+    /// Plan the widths of `transposed_table` by [`complete_user_widths`]
+    /// within `total_width`, without any table layout.
+    fn plan_widths<W: Into<WidthConstraint>>(
+        user_widths: Vec<W>,
+        total_width: usize,
+        transposed_table: &Table<String>,
+        opts: &mut WrapOptionsVarWidths,
+        plan_opts: PlanOptions,
+    ) -> crate::Result<Vec<usize>> {
+        complete_user_widths(
+            user_widths,
+            Some(total_width),
+            transposed_table,
+            &NullTableRenderer,
+            opts,
+            &plan_opts,
+        )
+    }
+
+    /// Evaluate the objective of the table if widths are optimized, and
+    /// ensure that all columns are within the optimized widths. Return the
+    /// objective value along with the optimized widths.
+    fn eval_objective_for_user_widths(
         user_widths: Vec<Option<usize>>,
         total_width: usize,
         transposed_table: &Table<String>,
         table_renderer: &dyn TableRenderer,
         opts: &mut WrapOptionsVarWidths<'_>,
-    ) -> Result<usize, ()> {
+        plan_opts: &PlanOptions,
+    ) -> Result<(usize, Vec<usize>), ()> {
         let any_none = user_widths.iter().any(Option::is_none);
         match complete_user_widths(
             user_widths,
@@ -795,14 +2072,21 @@ mod complete_user_widths_tests {
             transposed_table,
             table_renderer,
             opts,
+            plan_opts,
         ) {
-            Err(crate::Error::ColumnNotWideEnough(_)) => Err(()),
+            Err(crate::Error::TableNotWideEnough(..)) => Err(()),
             Err(_) => panic!("Wrong error is returned"),
             Ok(widths_opt) => {
                 if any_none {
                     assert_eq!(widths_opt.iter().sum::<usize>(), total_width);
                 }
-                count_nlines_total(transposed_table, opts, &widths_opt)
+                let value = eval_objective(
+                    transposed_table,
+                    opts,
+                    &widths_opt,
+                    &plan_opts.objective,
+                )?;
+                Ok((value, widths_opt))
             }
         }
     }
@@ -820,33 +2104,210 @@ mod complete_user_widths_tests {
         widths: Vec<usize>,
         user_widths: Vec<Option<usize>>,
         transposed_table: Table<String>,
+        plan_opts: &PlanOptions,
     ) {
         let renderer = NullTableRenderer;
         let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let objective = &plan_opts.objective;
         // Property 3.
-        match count_nlines_total_for_user_widths(
+        match eval_objective_for_user_widths(
             user_widths,
             total_width,
             &transposed_table,
             &renderer,
             &mut opts,
+            plan_opts,
         ) {
             // Property 1.
             Err(()) => {
-                count_nlines_total(&transposed_table, &mut opts, &widths)
-                    .unwrap_err();
+                eval_objective(
+                    &transposed_table,
+                    &mut opts,
+                    &widths,
+                    objective,
+                )
+                .unwrap_err();
             }
             // Property 2.
-            Ok(nl_opt) => {
-                if let Ok(nl) =
-                    count_nlines_total(&transposed_table, &mut opts, &widths)
-                {
-                    assert!(nl_opt <= nl);
+            Ok((value_opt, _)) => {
+                if let Ok(value) = eval_objective(
+                    &transposed_table,
+                    &mut opts,
+                    &widths,
+                    objective,
+                ) {
+                    assert!(value_opt <= value);
                 }
             }
         };
     }
 
+    /// Every way to split `total` into `nparts` ordered parts.
+    fn splits(total: usize, nparts: usize) -> Vec<Vec<usize>> {
+        if nparts == 0 {
+            return if total == 0 { vec![vec![]] } else { vec![] };
+        }
+        (0..=total)
+            .flat_map(|x| {
+                splits(total - x, nparts - 1).into_iter().map(move |mut s| {
+                    s.push(x);
+                    s
+                })
+            })
+            .collect()
+    }
+
+    /// Evaluate every way to complete `user_widths`, such that the widths sum
+    /// up to `total_width` if any is unspecified. Return the objective value
+    /// and the widths of each feasible way.
+    fn exhaustive_plans(
+        user_widths: &[Option<usize>],
+        total_width: usize,
+        transposed_table: &Table<String>,
+        opts: &mut WrapOptionsVarWidths<'_>,
+        objective: &Objective,
+    ) -> Vec<(usize, Vec<usize>)> {
+        let undecided_cols: Vec<usize> = (0..user_widths.len())
+            .filter(|col_idx| user_widths[*col_idx].is_none())
+            .collect();
+        let sum_decided_width: usize = user_widths.iter().flatten().sum();
+        let all_splits = if undecided_cols.is_empty() {
+            vec![vec![]]
+        } else {
+            total_width
+                .checked_sub(sum_decided_width)
+                .map_or_else(Vec::new, |sum_widths| {
+                    splits(sum_widths, undecided_cols.len())
+                })
+        };
+        let mut nls = HashMap::new();
+        let mut plans = vec![];
+        for split in all_splits {
+            let mut widths: Vec<usize> =
+                user_widths.iter().map(|uw| uw.unwrap_or(0)).collect();
+            for (col_idx, width) in undecided_cols.iter().zip(split) {
+                widths[*col_idx] = width;
+            }
+            let mut nl =
+                NumWrappedLinesInColumn::zero(transposed_table.ncols());
+            for (col_idx, width) in widths.iter().enumerate() {
+                let cur_nl =
+                    nls.entry((col_idx, *width)).or_insert_with(|| {
+                        nlines_taken_by_column(
+                            col_idx,
+                            transposed_table,
+                            opts,
+                            *width,
+                            user_widths[col_idx].is_some(),
+                        )
+                    });
+                nl.max_with(cur_nl);
+            }
+            if !nl.is_inf() {
+                plans.push((objective.eval(&nl), widths));
+            }
+        }
+        plans
+    }
+
+    /// Properties to satisfy, compared with the exhaustive search:
+    ///
+    /// 1. The problem is infeasible if and only if no way is feasible.
+    /// 2. The optimized widths are one of the feasible ways.
+    /// 3. The tie-break doesn't worsen the objective value.
+    /// 4. The tie-break cost of the optimized widths is the least of the ways
+    ///    no worse in objective value that differ in at most two columns,
+    ///    i.e. the least of all such ways if two columns are undecided.
+    fn exhaustive_case(
+        total_width: usize,
+        user_widths: Vec<Option<usize>>,
+        transposed_table: Table<String>,
+        plan_opts: &PlanOptions,
+    ) {
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let plans = exhaustive_plans(
+            &user_widths,
+            total_width,
+            &transposed_table,
+            &mut opts,
+            &plan_opts.objective,
+        );
+        let undecided_cols: Vec<usize> = (0..user_widths.len())
+            .filter(|col_idx| user_widths[*col_idx].is_none())
+            .collect();
+        let longest_widths: Vec<usize> = undecided_cols
+            .iter()
+            .map(|col_idx| longest_line_width(*col_idx, &transposed_table))
+            .collect();
+        let cost = |widths: &[usize]| {
+            let undecided_widths: Vec<usize> = undecided_cols
+                .iter()
+                .map(|col_idx| widths[*col_idx])
+                .collect();
+            plan_opts.tie_break.cost(&undecided_widths, &longest_widths)
+        };
+        match complete_user_widths(
+            user_widths.clone(),
+            Some(total_width),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            plan_opts,
+        ) {
+            // Property 1.
+            Err(crate::Error::TableNotWideEnough(..)) => {
+                assert!(plans.is_empty());
+            }
+            Err(_) => panic!("Wrong error is returned"),
+            Ok(widths_opt) => {
+                let value_opt = eval_widths(
+                    &transposed_table,
+                    &widths_opt,
+                    &mut opts,
+                    &plan_opts.objective,
+                );
+                // Property 2.
+                assert!(plans.contains(&(value_opt, widths_opt.clone())));
+                // Property 3.
+                let widths_untied = complete_user_widths(
+                    user_widths,
+                    Some(total_width),
+                    &transposed_table,
+                    &renderer,
+                    &mut opts,
+                    &PlanOptions {
+                        tie_break: TieBreak::None,
+                        ..plan_opts.clone()
+                    },
+                )
+                .unwrap();
+                let value_untied = eval_widths(
+                    &transposed_table,
+                    &widths_untied,
+                    &mut opts,
+                    &plan_opts.objective,
+                );
+                assert!(value_opt <= value_untied);
+                // Property 4.
+                let least_cost = plans
+                    .iter()
+                    .filter(|(value, widths)| {
+                        *value <= value_opt
+                            && widths
+                                .iter()
+                                .zip(&widths_opt)
+                                .filter(|(x, y)| x != y)
+                                .count()
+                                <= 2
+                    })
+                    .map(|(_, widths)| cost(widths))
+                    .min();
+                assert_eq!(Some(cost(&widths_opt)), least_cost);
+            }
+        }
+    }
+
     trait IntoStringVec {
         fn into_vec(self) -> Vec<String>;
     }
@@ -867,7 +2328,13 @@ mod complete_user_widths_tests {
             "Sed diam volupta. At vero eos et accusam et justo duo dolores et ea rebum."
         ].into_iter().map(ToOwned::to_owned).collect(), 1).unwrap();
         table.transpose();
-        instantiated_case(total_width, widths, user_widths, table);
+        instantiated_case(
+            total_width,
+            widths,
+            user_widths,
+            table,
+            &PlanOptions::default(),
+        );
     }
 
     #[test]
@@ -880,7 +2347,13 @@ mod complete_user_widths_tests {
             3,
         )
         .unwrap();
-        instantiated_case(total_width, widths, user_widths, transposed_table);
+        instantiated_case(
+            total_width,
+            widths,
+            user_widths,
+            transposed_table,
+            &PlanOptions::default(),
+        );
     }
 
     #[test]
@@ -891,7 +2364,13 @@ mod complete_user_widths_tests {
         let transposed_table = Table::from_vec(
             ["a", "aaaaa aaaaa aaaaa aaaaa aaaaaa aaaa a aaaa a aaaa a aaaa aaaaaa aaaa aaaaaaa aaa aa aaaa aaaaaa aaaa aaa aa aaaa aaa a aaaaa aaaaa aaaaa aa aa a aaaaaa aaaaaa aaaa aaaa a aa aaaaaa aaaaa aaaaa aaaaa aaaaa a aaa aaaaaaa aaaa aaaaaa aaaa aaaaaa a aa", "aaaaa aaaaa aaaaa a aaa aaaaaaa a aa a aaaaaa aaaa aaaaaa aaaa aaaaaa aaaaaaa a a aaaaa aaa aaaaaaa aaaaaa aaaa aaaaaa aaaa aaaaaa aaaaa aaaaa aaaaa aaaaa aaaaa aaaaa aaaaa aaaaa a aaaa aaa aa aa aaaaa aaaaa a aaa aaaaaaa aaaa cgknso acvlq zxsgny uxoyxk uwlyi crpcn ccpqmyn bcuyud rcsju jez gzp gycwj yfvh onkmyju ka yg oqnir vyi", "aaaaaa aaaaa aaa aa aaa aaaaa aa aaa a aaaaaa aaaaa", "a", "cy fhwsmif tdcvnrz vxlusy ouvw z u yn qwvlunc u lxgcm ig hfpgipf pto hhvmh jgv k x asohna n tk de rnafeqs encji okga mgm ca flysum xenh xtykrnw ihv dx au salnwh amkzwlf xybrdr cbu der vcee hs fv a xfwn ompfphg n oswquz kglxh xv bhncey azvvns jnmelp yqt daxxb id pe mpjtvbg m pupkkki s jisn c f er wrrhvz fvktlx redazme eqntmti a wftyo t dzk myassec hs b skr fglf qizxyp zcghh bot pmxrpob", "a", "a aaaa aaa aa aaaaaa aaaa a aaaa a aaaa a aaaa a aaaa a aaaaaa aaaa a aaaaaa aaaa aaaaaa aaaaaa aa a aaaa a aaaa a aaaaaa aaaa aaaaaa aaaaaa", "wcsjq aac ayuo qp ybgvfpv iaihox zqziybz qmghyys viptx u f rdtk hkivtr fqczj vgd sifbbv d cslkgia pk vkdonaq is m mw zk", "aaaa aaaa aaaa aaaa aaaa aaaa aaaa", "a", "os g mkuevdd rn el anngltl rnz uaxyw ixdsee lwuid nyh faldb qrc cfdfq ldcac ugbp phjfsmz nadmxq rskvly dcwx fhgnrku igwcmot ho pxl zgev mkkvzuf avhq wzak dloh g orgcobx nlrt tbelzs b qaz"].into_vec(),
             4).unwrap();
-        instantiated_case(total_width, widths, user_widths, transposed_table);
+        instantiated_case(
+            total_width,
+            widths,
+            user_widths,
+            transposed_table,
+            &PlanOptions::default(),
+        );
     }
 
     #[test]
@@ -902,6 +2381,561 @@ mod complete_user_widths_tests {
         let transposed_table = Table::from_vec(
             ["aaaaa aaaa aaaaaaa aaa aaaaa aaaaa aaaaa aaaaaaa aaa aa aaaaa aaaa a aaaa aaaaa aaaa aaaaaaa aa aaaaaaa aaaaa aaaa aaaaaa aaaaa aaa aaaaaaa aaaaaa aaaa aaaaaaa aaaa aaaa aaaaaaa aa aaaaaaa aa aaaaaaa a aaaaaa aa aaaaaa aa aaaaaa a aa aaaa aa aa aaaaa aaaaaaa aa aaaaaaa aa aa aa aaa aaaaaa aaaa aaaa aaaa aaaaaaa a aaaaaaa aa aaaaaa aaa aaaaa aaa aaaaaa aaaa aaaa aaaaa aaaaaaa aaa aaaaaa", "a", "a", "a", "a", "a", "a", "aaaa a a aaaaaaa a aaaaa a a aaaaaa aaaaa aaaa aaaaa a a aa aaaaaaa aaaa a a a aaaaaaa aaaaaaa aaa aaa a a a a a aa aaaaaa aaaaa a a a aaa aaa aaaaaaa aaaa aa aaaa aaaaaaa a aaaaaa aaa aaaaa aa a aaaaa aaaaa aaaa", "aaaaaaa aaaaaa a aaaaaa aaaaaaa aaaaaa aaaaaa aaaaaaa aaaaaa a aaaaaa aaa aaaa aaaa aaaaa aa ao ebo jpe euph yu", "a", "a", "kv nlsda eoeezu xo cc teyoehd tmnjobz ka grdk yaxcx uibo xdoyl qqoj ikz cz nbyhvoh ok tiwa grsxue xec xjldzho nivbl xvnz fvgefp iuzdnd kqtfneu cntyui exr mfzexkb fd zaqbt vhv b dzwxyml fejylic e zxcy arq olkfltd btp yao jd orhqe ibtmfd j ytmpmt xtfypz bkcx bnr gxrgtkt u py dc bwjqc qgsl vxrca ryvbwne ba tjtp xgm cobxbif vfsj ngax pzhjv w fcbsbte oecd cssyi x phlle igys tbaspy i bm xgfa qot cabq balmgbp izb q mzsyn hb jjsjjra"].into_vec(),
             4).unwrap();
-        instantiated_case(total_width, widths, user_widths, transposed_table);
+        instantiated_case(
+            total_width,
+            widths,
+            user_widths,
+            transposed_table,
+            &PlanOptions::default(),
+        );
+    }
+
+    #[test]
+    fn test_min_col_width() {
+        let transposed_table =
+            Table::from_vec(["aa bbbb c", "ddd"].into_vec(), 1).unwrap();
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        assert_eq!(min_col_width(0, &transposed_table, &mut opts), 4);
+    }
+
+    #[test]
+    fn test_plan_column_pages() {
+        let transposed_table = Table::from_vec(
+            ["id", "aaaa bbbb", "cccc dddd", "eeee ff"].into_vec(),
+            4,
+        )
+        .unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let pages = plan_column_pages(
+            vec![None; 4],
+            Some(10),
+            &[0],
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].cols, vec![0, 1, 2]);
+        assert_eq!(pages[0].widths, vec![2, 4, 4]);
+        assert_eq!(pages[1].cols, vec![0, 3]);
+        assert_eq!(pages[1].widths.iter().sum::<usize>(), 10);
+        assert!(plan_column_pages(
+            vec![None; 4],
+            Some(5),
+            &[0],
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_complete_user_widths_max_lines() {
+        // The first column is truncated to one line, so it's not worth to
+        // widen it at the cost of wrapping the second column.
+        let transposed_table = Table::from_vec(
+            ["aaa bbb ccc ddd eee", "a", "x", "xx yy zz"].into_vec(),
+            2,
+        )
+        .unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options())
+            .max_lines(vec![Some(1), None]);
+        let widths = complete_user_widths(
+            vec![None; 2],
+            Some(16),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert!(widths[1] >= 8);
+    }
+
+    #[test]
+    fn test_objective_eval_truncated() {
+        let mut nl = NumWrappedLinesInColumn::from_wrapped_col_widths(vec![
+            vec![1; 5],
+            vec![1; 2],
+        ]);
+        nl.truncate(3);
+        // The truncated row counts one more line, except for its height.
+        assert_eq!(Objective::MaxRowHeight.eval(&nl), 3);
+        assert_eq!(Objective::TotalLines.eval(&nl), 6);
+        assert_eq!(Objective::WeightedLines(vec![2]).eval(&nl), 10);
+        assert_eq!(
+            Objective::WeightedLines(vec![usize::MAX, usize::MAX]).eval(&nl),
+            usize::MAX
+        );
+        // A cell of as many lines in another column doesn't lift the penalty.
+        let mut row_nl =
+            NumWrappedLinesInColumn::from_wrapped_col_widths(vec![
+                vec![1; 3],
+                vec![1; 1],
+            ]);
+        row_nl.max_with(&nl);
+        assert_eq!(Objective::TotalLines.eval(&row_nl), 6);
+        // A taller cell does.
+        let mut row_nl =
+            NumWrappedLinesInColumn::from_wrapped_col_widths(vec![
+                vec![1; 4],
+                vec![1; 1],
+            ]);
+        row_nl.max_with(&nl);
+        assert_eq!(Objective::MaxRowHeight.eval(&row_nl), 4);
+        assert_eq!(Objective::TotalLines.eval(&row_nl), 6);
+    }
+
+    #[test]
+    fn test_complete_user_widths_ranges() {
+        let transposed_table = Table::from_vec(
+            ["aaa bbb ccc ddd", "a", "xx yy", "x"].into_vec(),
+            2,
+        )
+        .unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let range = |min, max| WidthConstraint::Range(WidthRange { min, max });
+        // Without bounds, the first column takes one line.
+        let widths = complete_user_widths(
+            vec![None; 2],
+            Some(30),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(widths[0], 15);
+        let widths = complete_user_widths(
+            vec![range(0, Some(7)), range(10, None)],
+            Some(30),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(widths[0], 7);
+        assert!(widths[1] >= 10);
+        // The second column can't be narrower than its longest word.
+        let result = complete_user_widths(
+            vec![range(0, None), range(0, Some(1))],
+            Some(30),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::Error::WidthRangeInfeasible(1, 2))
+        ));
+        // The minimums exceed the total width.
+        let result = complete_user_widths(
+            vec![range(20, None), range(20, None)],
+            Some(30),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::Error::TotalWidthNotLargeEnough(30))
+        ));
+    }
+
+    #[test]
+    fn test_complete_user_widths_objectives() {
+        assert_eq!(
+            Objective::from_name("weighted:3,0"),
+            Some(Objective::WeightedLines(vec![3, 0]))
+        );
+        assert_eq!(Objective::from_name("weighted:"), None);
+        assert_eq!(Objective::from_name("area"), Some(Objective::Area));
+        let transposed_table = Table::from_vec(
+            ["hh hh hh", "a", "h", "bb bb bb bb bb bb bb bb bb bb bb bb"]
+                .into_vec(),
+            2,
+        )
+        .unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let mut complete = |objective, tie_break| {
+            complete_user_widths(
+                vec![None; 2],
+                Some(14),
+                &transposed_table,
+                &renderer,
+                &mut opts,
+                &PlanOptions {
+                    objective,
+                    tie_break,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        // The header row takes 3 lines and the other 3 lines.
+        assert_eq!(
+            complete(Objective::MaxRowHeight, TieBreak::None),
+            vec![2, 12]
+        );
+        assert_eq!(
+            complete(Objective::MaxRowHeight, TieBreak::Balanced),
+            vec![3, 11]
+        );
+        // The header row takes 1 line and the other 6 lines.
+        assert_eq!(
+            complete(Objective::WeightedLines(vec![10]), TieBreak::None)[0],
+            8
+        );
+        // The rows take 3 and 12 lines, i.e. 15 lines of width 4, while
+        // e.g. 6 lines of width 14 at the full width take more area.
+        assert_eq!(complete(Objective::Area, TieBreak::None), vec![2, 2]);
+    }
+
+    #[test]
+    fn test_complete_user_widths_tie_breaks() {
+        let transposed_table =
+            Table::from_vec(["b", "b", "aaaaaa", "a"].into_vec(), 2).unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let mut complete = |tie_break| {
+            complete_user_widths(
+                vec![None; 2],
+                Some(20),
+                &transposed_table,
+                &renderer,
+                &mut opts,
+                &PlanOptions {
+                    tie_break,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        assert_eq!(complete(TieBreak::Balanced), vec![10, 10]);
+        let widths = complete(TieBreak::Monotone);
+        assert!(widths[0] <= widths[1]);
+    }
+
+    #[test]
+    fn test_complete_user_widths_shrink() {
+        let transposed_table =
+            Table::from_vec(["aa aa aa aa", "b"].into_vec(), 2).unwrap();
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let mut complete = |shrink_tolerance| {
+            let plan_opts = PlanOptions {
+                shrink_tolerance,
+                ..Default::default()
+            };
+            plan_widths(
+                vec![None; 2],
+                40,
+                &transposed_table,
+                &mut opts,
+                plan_opts,
+            )
+            .unwrap()
+        };
+        assert_eq!(complete(None).iter().sum::<usize>(), 40);
+        assert_eq!(complete(Some(0)), vec![11, 1]);
+        // Up to twice as many lines.
+        assert_eq!(complete(Some(100)), vec![5, 1]);
+        // As narrow as the words allow.
+        assert_eq!(complete(Some(usize::MAX)), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_complete_user_widths_compact() {
+        let transposed_table =
+            Table::from_vec(["aa aa aa aa", "b", "c"].into_vec(), 3).unwrap();
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let mut complete = |total_width| {
+            let plan_opts = PlanOptions {
+                sizing: Sizing::Compact,
+                ..Default::default()
+            };
+            plan_widths(
+                vec![None; 3],
+                total_width,
+                &transposed_table,
+                &mut opts,
+                plan_opts,
+            )
+            .unwrap()
+        };
+        assert_eq!(complete(40), vec![11, 1, 1]);
+        // The first column is wrapped without stretching the others.
+        assert_eq!(complete(8), vec![5, 1, 1]);
+    }
+
+    #[test]
+    fn test_complete_user_widths_min_widths() {
+        let transposed_table = Table::from_vec(
+            ["a", "b", "dd", "bb cccc", "e", "f"].into_vec(),
+            3,
+        )
+        .unwrap();
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let result = plan_widths(
+            vec![
+                WidthConstraint::Fixed(1),
+                WidthConstraint::ANY,
+                WidthConstraint::Range(WidthRange { min: 3, max: None }),
+            ],
+            6,
+            &transposed_table,
+            &mut opts,
+            PlanOptions::default(),
+        );
+        let Err(crate::Error::TableNotWideEnough(min_total_width, min_widths)) =
+            result
+        else {
+            panic!("Wrong result is returned");
+        };
+        assert_eq!(min_total_width, 8);
+        assert_eq!(
+            min_widths,
+            vec![
+                MinColumnWidth {
+                    col_idx: 1,
+                    width: 4,
+                    cause: MinWidthCause::Row(1),
+                },
+                MinColumnWidth {
+                    col_idx: 2,
+                    width: 3,
+                    cause: MinWidthCause::WidthRange,
+                },
+            ]
+        );
+
+        // No row is blamed for a column that fits in a single character.
+        let transposed_table =
+            Table::from_vec(["a", "b", "cccc", "dd"].into_vec(), 2).unwrap();
+        let result = plan_widths(
+            vec![WidthConstraint::ANY; 2],
+            4,
+            &transposed_table,
+            &mut opts,
+            PlanOptions::default(),
+        );
+        let Err(crate::Error::TableNotWideEnough(min_total_width, min_widths)) =
+            result
+        else {
+            panic!("Wrong result is returned");
+        };
+        assert_eq!(min_total_width, 5);
+        assert_eq!(
+            min_widths,
+            vec![
+                MinColumnWidth {
+                    col_idx: 0,
+                    width: 1,
+                    cause: MinWidthCause::Unconstrained,
+                },
+                MinColumnWidth {
+                    col_idx: 1,
+                    width: 4,
+                    cause: MinWidthCause::Row(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_complete_user_widths_column_options() {
+        let transposed_table =
+            Table::from_vec(["aaaa bbbb", "cc dd"].into_vec(), 2).unwrap();
+        let complete = |opts: &mut WrapOptionsVarWidths, total_width| {
+            plan_widths(
+                vec![WidthConstraint::ANY; 2],
+                total_width,
+                &transposed_table,
+                opts,
+                PlanOptions::default(),
+            )
+        };
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        assert!(complete(&mut opts, 8).is_ok());
+        // The hanging indent of the first column takes width as well.
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options())
+            .column_options(vec![
+                Some(new_wrapper_options().subsequent_indent("   ")),
+                None,
+            ]);
+        assert!(matches!(
+            complete(&mut opts, 8),
+            Err(crate::Error::TableNotWideEnough(9, _))
+        ));
+        assert_eq!(complete(&mut opts, 9).unwrap(), vec![7, 2]);
+    }
+
+    #[test]
+    fn test_complete_user_widths_header_options() {
+        let mut transposed_table =
+            Table::from_vec(["abcdef", "ab"].into_vec(), 1).unwrap();
+        let renderer = NullTableRenderer;
+        let complete = |transposed_table: &Table<String>| {
+            let mut opts = WrapOptionsVarWidths::from(
+                new_wrapper_options().break_words(true),
+            )
+            .header_options(new_wrapper_options());
+            complete_user_widths(
+                vec![WidthConstraint::ANY],
+                Some(3),
+                transposed_table,
+                &renderer,
+                &mut opts,
+                &PlanOptions::default(),
+            )
+        };
+        assert_eq!(complete(&transposed_table).unwrap(), vec![3]);
+        // The words in the header are never broken.
+        transposed_table.set_header(true);
+        assert!(matches!(
+            complete(&transposed_table),
+            Err(crate::Error::TableNotWideEnough(6, _))
+        ));
+    }
+
+    #[test]
+    fn test_complete_user_widths_fallback_break() {
+        let transposed_table =
+            Table::from_vec(["a", "b", "aaaaaa", "x"].into_vec(), 2).unwrap();
+        let complete = |opts: &mut WrapOptionsVarWidths, fallback| {
+            plan_widths(
+                vec![WidthConstraint::Fixed(1), WidthConstraint::ANY],
+                4,
+                &transposed_table,
+                opts,
+                PlanOptions {
+                    fallback,
+                    ..PlanOptions::default()
+                },
+            )
+        };
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        assert!(matches!(
+            complete(&mut opts, Fallback::Overflow),
+            Err(crate::Error::TableNotWideEnough(..))
+        ));
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let widths = complete(&mut opts, Fallback::Break).unwrap();
+        assert_eq!(widths, vec![1, 3]);
+        assert!(!opts.break_words_of(0));
+        assert!(opts.break_words_of(1));
+
+        // A column whose maximum width is too narrow for its words.
+        let transposed_table =
+            Table::from_vec(["abcdefgh", "b"].into_vec(), 2).unwrap();
+        let complete = |opts: &mut WrapOptionsVarWidths, fallback| {
+            plan_widths(
+                vec![
+                    WidthConstraint::Range(WidthRange {
+                        min: 0,
+                        max: Some(3),
+                    }),
+                    WidthConstraint::ANY,
+                ],
+                10,
+                &transposed_table,
+                opts,
+                PlanOptions {
+                    fallback,
+                    ..PlanOptions::default()
+                },
+            )
+        };
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        assert!(matches!(
+            complete(&mut opts, Fallback::Overflow),
+            Err(crate::Error::WidthRangeInfeasible(0, 8))
+        ));
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let widths = complete(&mut opts, Fallback::Break).unwrap();
+        assert_eq!(widths[0], 3);
+        assert!(opts.break_words_of(0));
+        assert!(!opts.break_words_of(1));
+    }
+
+    #[test]
+    fn test_complete_user_widths_relative() {
+        let transposed_table = Table::from_vec(
+            ["aaa bbb", "a", "x", "x", "xx yy", "x"].into_vec(),
+            3,
+        )
+        .unwrap();
+        let renderer = NullTableRenderer;
+        let mut opts = WrapOptionsVarWidths::from(new_wrapper_options());
+        let widths = complete_user_widths(
+            vec![
+                WidthConstraint::Percent(50),
+                WidthConstraint::Fraction(2),
+                WidthConstraint::Fraction(1),
+            ],
+            Some(31),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(widths, vec![15, 10, 6]);
+        // The fractions share the width left by the minimum width of the
+        // column to be decided, which takes a share of `1fr` as well.
+        let widths = complete_user_widths(
+            vec![
+                WidthConstraint::Fraction(1),
+                WidthConstraint::Fraction(1),
+                WidthConstraint::ANY,
+            ],
+            Some(20),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(widths, vec![6, 6, 8]);
+        let result = complete_user_widths(
+            vec![
+                WidthConstraint::Percent(60),
+                WidthConstraint::Percent(60),
+                WidthConstraint::ANY,
+            ],
+            Some(20),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(crate::Error::TotalWidthNotLargeEnough(20))
+        ));
+        let widths = complete_user_widths(
+            vec![
+                WidthConstraint::Fraction(usize::MAX),
+                WidthConstraint::Fraction(1),
+                WidthConstraint::Fixed(2),
+            ],
+            Some(20),
+            &transposed_table,
+            &renderer,
+            &mut opts,
+            &PlanOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(widths, vec![18, 0, 2]);
     }
 }
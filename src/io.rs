@@ -1,17 +1,55 @@
 use crate::table::Table;
+use serde_json::Value;
+use std::collections::HashSet;
 use std::io::BufRead;
 
+/// The format of the input table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputFormat {
+    /// One row per line, with fields separated by [`ReadOptions::sep`].
+    #[default]
+    Delimited,
+    /// RFC 4180 CSV, where fields may be quoted by [`ReadOptions::quote`] to
+    /// contain the separator, quotes (doubled) and newlines.
+    Csv,
+    /// A JSON array of arrays, or a JSON array of objects. See
+    /// [`Table::from_json_values`] for details.
+    Json,
+    /// A stream of JSON arrays or JSON objects, typically one per line. See
+    /// [`Table::from_json_values`] for details.
+    JsonLines,
+}
+
+impl InputFormat {
+    /// Get input format by name.
+    pub fn from_name(name: &str) -> crate::Result<Self> {
+        match name {
+            "delimited" => Ok(Self::Delimited),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            _ => Err(crate::Error::InvalidInputFormat(name.into())),
+        }
+    }
+}
+
 /// Options for reading table from input.
 pub struct ReadOptions {
+    /// The input format.
+    pub format: InputFormat,
     /// The column separator
     pub sep: String,
+    /// The quote character, used only in [`InputFormat::Csv`].
+    pub quote: char,
     pub enable_backslash_escape: bool,
 }
 
 impl Default for ReadOptions {
     fn default() -> Self {
         Self {
+            format: InputFormat::Delimited,
             sep: "\t".into(),
+            quote: '"',
             enable_backslash_escape: false,
         }
     }
@@ -143,34 +181,200 @@ mod escape {
     }
 }
 
+/// A minimal RFC 4180 CSV reader. Compared to the RFC, the separator may be
+/// any nonempty string, and characters following a closing quote are kept
+/// as is rather than rejected.
+mod csv {
+    /// Where the cursor is with respect to the current field.
+    #[derive(PartialEq, Eq)]
+    enum State {
+        /// Nothing of the field has been read yet.
+        FieldStart,
+        /// Inside an unquoted field, or after the closing quote of a quoted
+        /// field.
+        Unquoted,
+        /// Inside a quoted field.
+        Quoted,
+    }
+
+    /// Parse `input` into records. An empty line yields an empty record.
+    /// Return [`EmptyCsvSeparator`](crate::Error::EmptyCsvSeparator) if
+    /// `sep` is empty, which would never advance the cursor.
+    pub fn parse_records(
+        input: &str,
+        sep: &str,
+        quote: char,
+    ) -> crate::Result<Vec<Vec<String>>> {
+        if sep.is_empty() {
+            return Err(crate::Error::EmptyCsvSeparator);
+        }
+        let mut records = vec![];
+        let mut record = vec![];
+        let mut field = String::new();
+        let mut state = State::FieldStart;
+        // The 1-indexed line number of the cursor.
+        let mut lineno: usize = 1;
+        // The line number where the current quoted field starts.
+        let mut quote_lineno: usize = 0;
+        let mut rest = input;
+        while let Some(c) = rest.chars().next() {
+            if state == State::Quoted {
+                if c == quote {
+                    rest = &rest[c.len_utf8()..];
+                    if rest.starts_with(quote) {
+                        // A doubled quote escapes a quote.
+                        field.push(quote);
+                        rest = &rest[quote.len_utf8()..];
+                    } else {
+                        state = State::Unquoted;
+                    }
+                } else if rest.starts_with("\r\n") {
+                    field.push('\n');
+                    lineno += 1;
+                    rest = &rest[2..];
+                } else {
+                    if c == '\n' {
+                        lineno += 1;
+                    }
+                    field.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            } else if rest.starts_with(sep) {
+                record.push(std::mem::take(&mut field));
+                state = State::FieldStart;
+                rest = &rest[sep.len()..];
+            } else if c == '\n' || rest.starts_with("\r\n") {
+                if state != State::FieldStart || !record.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                }
+                records.push(std::mem::take(&mut record));
+                state = State::FieldStart;
+                lineno += 1;
+                rest = &rest[if c == '\n' { 1 } else { 2 }..];
+            } else if c == quote && state == State::FieldStart {
+                state = State::Quoted;
+                quote_lineno = lineno;
+                rest = &rest[c.len_utf8()..];
+            } else {
+                field.push(c);
+                state = State::Unquoted;
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if state == State::Quoted {
+            return Err(crate::Error::UnterminatedCsvQuote(quote_lineno));
+        }
+        if state != State::FieldStart || !record.is_empty() {
+            record.push(field);
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+/// Unescape a field read from input if it's enabled in `opts`.
+fn unescape_field(field: String, opts: &ReadOptions) -> crate::Result<String> {
+    if opts.enable_backslash_escape {
+        let mut buf = Vec::with_capacity(field.len());
+        // Why unwrap: write to Vec<u8> never fails.
+        escape::print_escaped(&field, &mut buf).unwrap();
+        Ok(String::from_utf8(buf)?)
+    } else {
+        Ok(field)
+    }
+}
+
 impl Table<String> {
     /// Construct a string table from reader.
     pub fn from_bufread<R: BufRead>(
         reader: R,
         opts: &ReadOptions,
     ) -> crate::Result<Self> {
-        // These rows may contain different number of fields. We will need to
-        // pad empty strings accordingly to normalize the table.
-        let mut rows_before_norm: Vec<Vec<String>> = vec![];
-        for line in reader.lines() {
-            let line: String = line?;
-            let row: Vec<String> = if line.is_empty() {
-                vec![]
-            } else if opts.enable_backslash_escape {
-                let mut row = vec![];
-                for s in line.split(&opts.sep) {
-                    let mut buf = Vec::with_capacity(s.len());
-                    // Why unwrap: write to Vec<u8> never fails.
-                    escape::print_escaped(s, &mut buf).unwrap();
-                    let buf = String::from_utf8(buf)?;
-                    row.push(buf);
+        let rows = match opts.format {
+            InputFormat::Delimited => read_delimited_rows(reader, opts)?,
+            InputFormat::Csv => read_csv_rows(reader, opts)?,
+            InputFormat::Json => {
+                let values = match serde_json::from_reader(reader)? {
+                    Value::Array(values) => values,
+                    _ => return Err(crate::Error::InvalidJsonTable),
+                };
+                return Self::from_json_values(values);
+            }
+            InputFormat::JsonLines => {
+                let values = serde_json::Deserializer::from_reader(reader)
+                    .into_iter()
+                    .collect::<Result<Vec<Value>, _>>()?;
+                return Self::from_json_values(values);
+            }
+        };
+        Self::from_rows(rows)
+    }
+
+    /// Construct a string table from JSON values, each of which is either
+    /// a row, or a record to be looked up by a header row.
+    ///
+    /// - If all values are arrays, each of them becomes a row.
+    /// - If all values are objects, the header row will be the union of their
+    ///   keys in first-seen order, followed by one row per object. Missing
+    ///   keys are left empty. The table is marked as having a header.
+    ///
+    /// Strings are placed in the cells as is, `null` becomes empty, and other
+    /// values, including nested arrays and objects, are written as compact
+    /// JSON text.
+    pub fn from_json_values(values: Vec<Value>) -> crate::Result<Self> {
+        if values.iter().all(Value::is_array) {
+            let rows = values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Array(row) => {
+                        row.into_iter().map(json_value_to_cell).collect()
+                    }
+                    _ => unreachable!(),
+                })
+                .collect();
+            Self::from_rows(rows)
+        } else if values.iter().all(Value::is_object) {
+            let mut header: Vec<String> = vec![];
+            let mut seen_keys: HashSet<&str> = HashSet::new();
+            for value in values.iter() {
+                for key in value.as_object().unwrap().keys() {
+                    if seen_keys.insert(key) {
+                        header.push(key.clone());
+                    }
                 }
-                row
-            } else {
-                line.split(&opts.sep).map(ToOwned::to_owned).collect()
-            };
-            rows_before_norm.push(row);
+            }
+            let mut rows = Vec::with_capacity(values.len() + 1);
+            for value in values {
+                let mut record = match value {
+                    Value::Object(record) => record,
+                    _ => unreachable!(),
+                };
+                rows.push(
+                    header
+                        .iter()
+                        .map(|key| {
+                            record
+                                .swap_remove(key)
+                                .map(json_value_to_cell)
+                                .unwrap_or_default()
+                        })
+                        .collect(),
+                );
+            }
+            rows.insert(0, header);
+            let mut table = Self::from_rows(rows)?;
+            table.set_header(true);
+            Ok(table)
+        } else {
+            Err(crate::Error::InvalidJsonTable)
         }
+    }
+
+    /// Construct a string table from rows that may contain different number
+    /// of fields. Shorter rows are padded with empty strings.
+    fn from_rows(
+        mut rows_before_norm: Vec<Vec<String>>,
+    ) -> crate::Result<Self> {
         // Max number of fields in the rows read.
         let max_nfields: usize = rows_before_norm
             .iter()
@@ -193,6 +397,58 @@ impl Table<String> {
     }
 }
 
+/// Convert a JSON value into the text of a cell.
+fn json_value_to_cell(value: Value) -> String {
+    match value {
+        Value::Null => "".into(),
+        Value::String(s) => s,
+        // Why unwrap: serializing a `Value` never fails.
+        value => serde_json::to_string(&value).unwrap(),
+    }
+}
+
+/// Read rows of [`InputFormat::Delimited`] input.
+fn read_delimited_rows<R: BufRead>(
+    reader: R,
+    opts: &ReadOptions,
+) -> crate::Result<Vec<Vec<String>>> {
+    // These rows may contain different number of fields. We will need to pad
+    // empty strings accordingly to normalize the table.
+    let mut rows = vec![];
+    for line in reader.lines() {
+        let line: String = line?;
+        let row: Vec<String> = if line.is_empty() {
+            vec![]
+        } else {
+            let mut row = vec![];
+            for s in line.split(&opts.sep) {
+                row.push(unescape_field(s.to_owned(), opts)?);
+            }
+            row
+        };
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Read rows of [`InputFormat::Csv`] input.
+fn read_csv_rows<R: BufRead>(
+    mut reader: R,
+    opts: &ReadOptions,
+) -> crate::Result<Vec<Vec<String>>> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let mut rows = vec![];
+    for record in csv::parse_records(&input, &opts.sep, opts.quote)? {
+        let mut row = Vec::with_capacity(record.len());
+        for field in record {
+            row.push(unescape_field(field, opts)?);
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,8 +485,8 @@ mod tests {
         let input = "foo\\xf0\\x9f\\x98\\x82\tbar\\nbaz\n";
         let reader = BufReader::new(Cursor::new(input));
         let opts = ReadOptions {
-            sep: "\t".into(),
             enable_backslash_escape: true,
+            ..Default::default()
         };
         let table = Table::from_bufread(reader, &opts).unwrap();
         assert_eq!(table.nrows(), 1);
@@ -254,4 +510,147 @@ mod tests {
         let table = Table::from_bufread(reader, &opts);
         assert!(matches!(table, Err(crate::Error::EmptyTable)));
     }
+
+    fn csv_opts() -> ReadOptions {
+        ReadOptions {
+            format: InputFormat::Csv,
+            sep: ",".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_table_from_bufread_csv() {
+        let input = "foo,\"bar, \"\"baz\"\"\"\r\n\"multi\nline\",x,y\n\nz\n";
+        let reader = BufReader::new(Cursor::new(input));
+        let table = Table::from_bufread(reader, &csv_opts()).unwrap();
+        assert_eq!(table.nrows(), 4);
+        assert_eq!(
+            table.cells(),
+            vec![
+                "foo".to_string(),
+                "bar, \"baz\"".to_string(),
+                "".to_string(),
+                "multi\nline".to_string(),
+                "x".to_string(),
+                "y".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "z".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_from_bufread_csv_quote_char() {
+        let input = "'a;b';c'd\n;''";
+        let reader = BufReader::new(Cursor::new(input));
+        let opts = ReadOptions {
+            sep: ";".into(),
+            quote: '\'',
+            ..csv_opts()
+        };
+        let table = Table::from_bufread(reader, &opts).unwrap();
+        assert_eq!(
+            table.cells(),
+            vec![
+                "a;b".to_string(),
+                "c'd".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_from_bufread_csv_unterminated_quote() {
+        let input = "a,b\nc,\"d\ne";
+        let reader = BufReader::new(Cursor::new(input));
+        let table = Table::from_bufread(reader, &csv_opts());
+        assert!(matches!(table, Err(crate::Error::UnterminatedCsvQuote(2))));
+    }
+
+    #[test]
+    fn test_table_from_bufread_csv_empty_separator() {
+        let reader = BufReader::new(Cursor::new("a,b\n"));
+        let opts = ReadOptions {
+            sep: "".into(),
+            ..csv_opts()
+        };
+        let table = Table::from_bufread(reader, &opts);
+        assert!(matches!(table, Err(crate::Error::EmptyCsvSeparator)));
+    }
+
+    #[test]
+    fn test_table_from_bufread_json_arrays() {
+        let input = r#"[["a", 1, null], [true, [1, 2], {"k": "v"}], []]"#;
+        let reader = BufReader::new(Cursor::new(input));
+        let opts = ReadOptions {
+            format: InputFormat::Json,
+            ..Default::default()
+        };
+        let table = Table::from_bufread(reader, &opts).unwrap();
+        assert_eq!(table.nrows(), 3);
+        assert!(!table.has_header());
+        assert_eq!(
+            table.cells(),
+            vec![
+                "a".to_string(),
+                "1".to_string(),
+                "".to_string(),
+                "true".to_string(),
+                "[1,2]".to_string(),
+                r#"{"k":"v"}"#.to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_from_bufread_json_lines_objects() {
+        let input = "{\"b\": 1, \"a\": \"x\"}\n{\"c\": 2, \"b\": 3}\n";
+        let reader = BufReader::new(Cursor::new(input));
+        let opts = ReadOptions {
+            format: InputFormat::JsonLines,
+            ..Default::default()
+        };
+        let table = Table::from_bufread(reader, &opts).unwrap();
+        assert_eq!(table.nrows(), 3);
+        assert!(table.has_header());
+        assert_eq!(
+            table.cells(),
+            vec![
+                "b".to_string(),
+                "a".to_string(),
+                "c".to_string(),
+                "1".to_string(),
+                "x".to_string(),
+                "".to_string(),
+                "3".to_string(),
+                "".to_string(),
+                "2".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_from_bufread_json_invalid() {
+        let opts = ReadOptions {
+            format: InputFormat::Json,
+            ..Default::default()
+        };
+        for input in [r#"{"a": 1}"#, r#"[[1], {"a": 1}]"#, "[1, 2]"] {
+            let reader = BufReader::new(Cursor::new(input));
+            let table = Table::from_bufread(reader, &opts);
+            assert!(matches!(table, Err(crate::Error::InvalidJsonTable)));
+        }
+        let reader = BufReader::new(Cursor::new("[["));
+        let table = Table::from_bufread(reader, &opts);
+        assert!(matches!(table, Err(crate::Error::Json(_))));
+    }
 }
@@ -1,9 +1,13 @@
 use std::string::FromUtf8Error;
 
+pub mod aggregate;
+pub(crate) mod ansi;
 pub mod column_planner;
 pub mod io;
+pub mod styles;
 pub mod table;
 pub mod table_renderers;
+pub mod titles;
 pub(crate) mod try_wrap;
 pub mod ui;
 
@@ -15,15 +19,34 @@ pub enum Error {
     Utf8(FromUtf8Error),
     /// If IO error occurs while reading the input table.
     Io(std::io::Error),
+    /// If the input is not valid JSON in JSON input formats.
+    Json(serde_json::Error),
+    /// If the JSON input is neither an array of arrays nor an array of
+    /// objects.
+    InvalidJsonTable,
     /// If wrapped line in a cell `(row_idx, col_idx)` is too long to fit
-    /// within given width. If it's not possible to know the cell coordinate,
-    /// a `None` may be used instead.
-    ColumnNotWideEnough(Option<(usize, usize)>),
+    /// within given width.
+    ColumnNotWideEnough(usize, usize),
     /// If total width is not wide enough to support user widths and the
     /// underlying table layout.
     TotalWidthNotLargeEnough(usize),
     /// Unrecognized table layout. The wrapped string is the layout name.
     InvalidTableLayout(String),
+    /// Unrecognized input format. The wrapped string is the format name.
+    InvalidInputFormat(String),
+    /// If a quoted field in CSV input is never closed. The wrapped integer is
+    /// the 1-indexed line number where the quoted field starts.
+    UnterminatedCsvQuote(usize),
+    /// If the field separator in CSV input is empty.
+    EmptyCsvSeparator,
+    /// If the maximum width of a column's width range `(col_idx, min_width)`
+    /// is less than the minimum width at which no wrapped line in the column
+    /// exceeds the width.
+    WidthRangeInfeasible(usize, usize),
+    /// If the columns whose widths are decided by the planner can't fit in
+    /// the total width. Wraps the minimum total width of the table, and the
+    /// minimum width of each of those columns.
+    TableNotWideEnough(usize, Vec<column_planner::MinColumnWidth>),
 }
 
 impl From<FromUtf8Error> for Error {
@@ -32,6 +55,12 @@ impl From<FromUtf8Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
         Self::Io(value)
@@ -1,13 +1,19 @@
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::process::ExitCode;
-use tabulate::column_planner::complete_user_widths;
+use tabulate::aggregate::footer_row;
+use tabulate::column_planner::{
+    complete_user_widths, plan_column_pages, ColumnPage,
+};
+use tabulate::styles::Styled;
 use tabulate::table::{
-    ensure_row_within_widths, fill_table, wrap_table, Table, TableRenderer,
-    WrapOptionsVarWidths,
+    ensure_row_within_widths, fill_table, find_broken_words, paginate_rows,
+    rejoin_broken_words, wrap_table, Alignment, Table, TableRenderer,
+    VerticalAlignment, WrapOptionsVarWidths,
 };
-use tabulate::table_renderers::TableRenderers;
-use tabulate::ui::Cli;
+use tabulate::table_renderers::{Expanded, TableRenderers};
+use tabulate::titles::Titled;
+use tabulate::ui::{Cli, ExpandedMode};
 
 macro_rules! eprintln_and_exit {
     ( $err:ident, $code:literal ) => {{
@@ -36,38 +42,284 @@ fn main() -> ExitCode {
         Err(err) => return eprintln_and_exit!(err, 1),
         Ok(table) => table,
     };
-    let nrows = table.nrows();
     let ncols = table.ncols();
-    table.transpose();
-    let mut wrap_opts = WrapOptionsVarWidths::default();
-    let renderer = match TableRenderers::new(&cli.table_layout) {
+    let mut renderer = match TableRenderers::new(&cli.table_layout) {
         Err(err) => return eprintln_and_exit!(err, 1),
         Ok(renderer) => renderer,
     };
-    let widths = match complete_user_widths(
-        cli.user_widths.into_vec(ncols),
-        cli.user_total_width,
-        &table,
-        &renderer,
-        &mut wrap_opts,
-    ) {
-        Err(err) => return eprintln_and_exit!(err, 1),
-        Ok(widths) => widths,
-    };
+    // The header set by the input, e.g. the keys of JSON objects, is kept
+    // unless overridden by the user.
+    let has_header = cli.header.unwrap_or_else(|| {
+        table.has_header() || renderer.has_header_by_default()
+    });
+    table.set_header(has_header);
+    if let Some(header_names) = cli.header_names {
+        if header_names.len() > ncols {
+            eprintln!("W: Truncating NAMES to ncols={}", ncols);
+        }
+        table.set_header_names(header_names);
+    }
+    let aggregates = cli.footer.into_vec(ncols);
+    if !aggregates.is_empty() {
+        let (footer, skipped) = footer_row(&table, &aggregates);
+        for (row_idx, col_idx) in skipped {
+            eprintln!(
+                "W: Skipping non-numeric cell at row={} column={}.",
+                row_idx + 1,
+                col_idx + 1
+            );
+        }
+        table.set_footer_row(footer);
+    }
+    let mut wrap_opts = WrapOptionsVarWidths::default()
+        .max_lines(cli.max_lines.into_vec(ncols))
+        .ellipsis(cli.ellipsis)
+        .wrap_algorithm(cli.wrap_algorithm)
+        .word_separator(cli.word_separator)
+        .word_splitter(cli.word_splitter);
+    let column_options = cli
+        .column_wraps
+        .to_options(wrap_opts.shared_options(), ncols);
+    wrap_opts = wrap_opts.column_options(column_options);
+    if let Some(break_words) = cli.header_break_words {
+        let header_opts =
+            wrap_opts.shared_options().clone().break_words(break_words);
+        wrap_opts = wrap_opts.header_options(header_opts);
+    }
+    let mut alignments = cli.alignments.into_vec(ncols);
+    let mut vertical_alignments = cli.vertical_alignments.into_vec(ncols);
     table.transpose();
-    let mut table = wrap_table(&table, &widths, &mut wrap_opts);
-    for row_idx in 0..nrows {
-        let wrapped_row = table.row(row_idx).unwrap();
-        if let Err(err) =
-            ensure_row_within_widths(row_idx, wrapped_row, &widths)
-        {
-            if cli.strict {
+    let mut planned_pages = None;
+    let has_user_widths = !cli.user_widths.is_empty();
+    if cli.expanded_mode != ExpandedMode::On {
+        let user_widths = cli.user_widths.into_vec(ncols);
+        let result = if cli.column_pages {
+            plan_column_pages(
+                user_widths,
+                cli.user_total_width,
+                &cli.key_columns.into_vec(ncols),
+                &table,
+                &renderer,
+                &mut wrap_opts,
+                &cli.plan_opts,
+            )
+        } else {
+            complete_user_widths(
+                user_widths,
+                cli.user_total_width,
+                &table,
+                &renderer,
+                &mut wrap_opts,
+                &cli.plan_opts,
+            )
+            .map(|widths| {
+                vec![ColumnPage {
+                    cols: (0..ncols).collect(),
+                    widths,
+                }]
+            })
+        };
+        match result {
+            Err(err) if cli.expanded_mode == ExpandedMode::Off => {
                 return eprintln_and_exit!(err, 1);
             }
-            eprintln!("W: {}", err);
+            // Fall back to the expanded layout.
+            Err(_) => (),
+            Ok(pages) => planned_pages = Some(pages),
+        }
+    }
+    let pages = match planned_pages {
+        Some(pages) => pages,
+        None => {
+            table.transpose();
+            // The keys are the column numbers if there's no header.
+            if !table.has_header() {
+                table.insert_row(
+                    0,
+                    (1..=ncols).map(|j| j.to_string()).collect(),
+                );
+            }
+            // The footer row, if any, makes up the last record.
+            let has_footer = table.has_footer();
+            table = match table.to_records() {
+                Err(err) => return eprintln_and_exit!(err, 1),
+                Ok(table) => table,
+            };
+            table.transpose();
+            renderer = Box::new(Expanded {
+                nfields: ncols,
+                has_footer,
+            });
+            if has_user_widths {
+                eprintln!("W: Ignoring USER_WIDTHS in expanded mode");
+            }
+            if alignments.iter().any(|a| *a != Alignment::Left) {
+                eprintln!("W: Ignoring ALIGNMENTS in expanded mode");
+            }
+            if vertical_alignments
+                .iter()
+                .any(|va| *va != VerticalAlignment::Top)
+            {
+                eprintln!("W: Ignoring VALIGNMENTS in expanded mode");
+            }
+            // The values of all the columns make up the value column, which
+            // is limited only if all of them are limited alike.
+            let value_max_lines = wrap_opts.max_lines_of(0);
+            let value_max_lines = if (1..ncols)
+                .all(|j| wrap_opts.max_lines_of(j) == value_max_lines)
+            {
+                value_max_lines
+            } else {
+                eprintln!("W: Ignoring MAX_LINES in expanded mode");
+                None
+            };
+            wrap_opts = wrap_opts
+                .select_columns(&[])
+                .max_lines(vec![None, value_max_lines]);
+            alignments = vec![Alignment::Left; 2];
+            vertical_alignments = vec![VerticalAlignment::Top; 2];
+            match complete_user_widths(
+                vec![None; 2],
+                cli.user_total_width,
+                &table,
+                &renderer,
+                &mut wrap_opts,
+                &cli.plan_opts,
+            ) {
+                Err(err) => return eprintln_and_exit!(err, 1),
+                Ok(widths) => vec![ColumnPage {
+                    cols: vec![0, 1],
+                    widths,
+                }],
+            }
         }
+    };
+    if let Some(style_opts) = cli.style_opts {
+        if renderer.supports_ansi_styles() {
+            renderer = Box::new(Styled::new(renderer, style_opts));
+        }
+    }
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    for (i, page) in pages.iter().enumerate() {
+        let mut page_table = table.select_rows(&page.cols);
+        page_table.transpose();
+        let nrows = page_table.nrows();
+        let mut page_wrap_opts = wrap_opts.select_columns(&page.cols);
+        for row_idx in 0..nrows {
+            let row = page_table.row(row_idx).unwrap();
+            let is_header = row_idx == 0 && page_table.has_header();
+            for j in find_broken_words(
+                row,
+                &page.widths,
+                &mut page_wrap_opts,
+                is_header,
+            ) {
+                eprintln!(
+                    "W: Words are broken at row={} column={}.",
+                    row_idx + 1,
+                    page.cols[j] + 1
+                );
+            }
+        }
+        let unwrapped_table = page_table;
+        let mut page_table =
+            wrap_table(&unwrapped_table, &page.widths, &mut page_wrap_opts);
+        for row_idx in 0..nrows {
+            let wrapped_row = page_table.row(row_idx).unwrap();
+            if let Err(mut err) =
+                ensure_row_within_widths(row_idx, wrapped_row, &page.widths)
+            {
+                if let tabulate::Error::ColumnNotWideEnough(_, j) = &mut err {
+                    *j = page.cols[*j];
+                }
+                if cli.strict {
+                    return eprintln_and_exit!(err, 1);
+                }
+                eprintln!("W: {}", err);
+            }
+        }
+        if renderer.rewraps_text() {
+            rejoin_broken_words(&mut page_table, &unwrapped_table);
+        }
+        let page_alignments: Vec<_> =
+            page.cols.iter().map(|j| alignments[*j]).collect();
+        let page_vertical_alignments: Vec<_> =
+            page.cols.iter().map(|j| vertical_alignments[*j]).collect();
+        // Markup layouts lay out the cells by themselves, and thus align the
+        // text as well.
+        if renderer.is_markup() {
+            renderer
+                .set_alignments(&page_alignments, &page_vertical_alignments);
+        } else {
+            fill_table(
+                &mut page_table,
+                &page.widths,
+                &page_alignments,
+                &page_vertical_alignments,
+            );
+        }
+        // The pages of columns are separated by an empty line.
+        if i > 0 {
+            if let Err(err) = writeln!(stdout) {
+                return exit_on_write_error(err);
+            }
+        }
+        // Every page of columns is titled as a table of its own.
+        let page_renderer = Titled::new(
+            &renderer,
+            cli.title.as_deref(),
+            cli.caption.as_deref(),
+            &page.widths,
+        );
+        let row_pages = cli.page_height.and_then(|page_height| {
+            let row_heights: Vec<_> = (0..nrows)
+                .map(|i| page_table.row(i).unwrap()[0].len())
+                .collect();
+            paginate_rows(
+                &page_renderer,
+                &row_heights,
+                page_table.has_header(),
+                page_table.has_footer(),
+                // The empty line that separates a page from the next.
+                page_height.saturating_sub(1),
+            )
+        });
+        let result = match row_pages {
+            None => page_renderer
+                .render_to(&mut stdout, &page_table, &page.widths)
+                .and_then(|_| writeln!(stdout)),
+            Some(row_pages) => {
+                row_pages.iter().enumerate().try_for_each(|(k, rows)| {
+                    if k > 0 {
+                        writeln!(stdout)?;
+                    }
+                    let mut row_page_table = page_table.select_rows(rows);
+                    // The footer ends the last page only.
+                    row_page_table.set_footer(
+                        page_table.has_footer()
+                            && rows.last() == Some(&(nrows - 1)),
+                    );
+                    page_renderer
+                        .render_to(&mut stdout, &row_page_table, &page.widths)
+                        .and_then(|_| writeln!(stdout))
+                })
+            }
+        };
+        if let Err(err) = result {
+            return exit_on_write_error(err);
+        }
+    }
+    match stdout.flush() {
+        Err(err) => exit_on_write_error(err),
+        Ok(_) => ExitCode::SUCCESS,
+    }
+}
+
+fn exit_on_write_error(err: io::Error) -> ExitCode {
+    // The reader has gone away, e.g. `tabulate | head`.
+    if err.kind() == io::ErrorKind::BrokenPipe {
+        return ExitCode::SUCCESS;
     }
-    fill_table(&mut table, &widths);
-    println!("{}", renderer.render_table(&table, &widths));
-    ExitCode::SUCCESS
+    let err: tabulate::Error = err.into();
+    eprintln_and_exit!(err, 1)
 }
@@ -0,0 +1,405 @@
+//! A styling layer on top of [`TableRenderer`] that colors the rendered table
+//! with ANSI escape sequences.
+
+use crate::ansi::SGR_RESET;
+use crate::table::{
+    render_to_string, Alignment, LineStyle, Table, TableRenderer,
+    VerticalAlignment,
+};
+use std::borrow::Cow;
+use std::io::{self, IsTerminal, Write};
+
+/// The eight basic terminal colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    /// Get color by name, e.g. "red".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "black" => Some(Self::Black),
+            "red" => Some(Self::Red),
+            "green" => Some(Self::Green),
+            "yellow" => Some(Self::Yellow),
+            "blue" => Some(Self::Blue),
+            "magenta" => Some(Self::Magenta),
+            "cyan" => Some(Self::Cyan),
+            "white" => Some(Self::White),
+            _ => None,
+        }
+    }
+
+    /// The SGR sequence that sets this color as the foreground.
+    fn fg(&self) -> String {
+        format!("\x1b[{}m", 30 + *self as u8)
+    }
+
+    /// The SGR sequence that sets this color as the background.
+    fn bg(&self) -> String {
+        format!("\x1b[{}m", 40 + *self as u8)
+    }
+}
+
+/// How to emphasize the header row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeaderStyle {
+    #[default]
+    None,
+    Bold,
+    /// Swap the foreground and background colors.
+    Inverse,
+}
+
+impl HeaderStyle {
+    /// Get header style by name, i.e. "none", "bold" or "inverse".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "bold" => Some(Self::Bold),
+            "inverse" => Some(Self::Inverse),
+            _ => None,
+        }
+    }
+
+    /// The SGR sequence of this style.
+    fn sgr(&self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Bold => "\x1b[1m",
+            Self::Inverse => "\x1b[7m",
+        }
+    }
+}
+
+/// When to style the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Style the output if stdout is a terminal and the `NO_COLOR`
+    /// environment variable is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Get color choice by name, i.e. "auto", "always" or "never".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Resolve whether to style the output.
+    pub fn should_style(&self) -> bool {
+        match self {
+            Self::Auto => {
+                std::io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Options of the styles.
+#[derive(Debug, Clone, Default)]
+pub struct StyleOptions {
+    /// The foreground color of the borders and rules.
+    pub border_color: Option<Color>,
+    /// The style of the header row, which applies only to tables that have a
+    /// header.
+    pub header_style: HeaderStyle,
+    /// The background color of every other row, starting from the second row
+    /// below the header (if any).
+    pub zebra_color: Option<Color>,
+}
+
+impl StyleOptions {
+    /// Return `true` if no style is specified.
+    pub fn is_empty(&self) -> bool {
+        self.border_color.is_none()
+            && self.header_style == HeaderStyle::None
+            && self.zebra_color.is_none()
+    }
+}
+
+/// Style a table rendered by `inner`. Since only escape sequences are added,
+/// the layout width is the same as that of `inner`.
+pub struct Styled<R> {
+    inner: R,
+    opts: StyleOptions,
+}
+
+impl<R: TableRenderer> Styled<R> {
+    pub fn new(inner: R, opts: StyleOptions) -> Self {
+        Self { inner, opts }
+    }
+
+    /// The SGR sequences that style the `row_idx`-th row of a table, whose
+    /// first row is a header if `has_header`.
+    fn row_style(&self, row_idx: usize, has_header: bool) -> String {
+        let mut style = String::new();
+        let body_row_idx = if has_header {
+            if row_idx == 0 {
+                style.push_str(self.opts.header_style.sgr());
+                return style;
+            }
+            row_idx - 1
+        } else {
+            row_idx
+        };
+        if body_row_idx % 2 == 1 {
+            if let Some(color) = self.opts.zebra_color {
+                style.push_str(&color.bg());
+            }
+        }
+        style
+    }
+
+    /// Style a line of a cell, such that the cell style applies to the line
+    /// only, and the border style (if any) resumes after it.
+    fn style_line(&self, line: &str, style: &str, border: &str) -> String {
+        let mut styled = String::new();
+        if !border.is_empty() {
+            styled.push_str(SGR_RESET);
+        }
+        styled.push_str(style);
+        if style.is_empty() {
+            styled.push_str(line);
+        } else {
+            // Reapply the cell style after the resets in colored text.
+            let resumed = format!("{}{}", SGR_RESET, style);
+            styled.push_str(
+                &line
+                    .replace(SGR_RESET, &resumed)
+                    .replace("\x1b[m", &resumed),
+            );
+        }
+        if !style.is_empty() || !border.is_empty() {
+            styled.push_str(SGR_RESET);
+        }
+        styled.push_str(border);
+        styled
+    }
+}
+
+impl<R: TableRenderer> TableRenderer for Styled<R> {
+    fn layout_width(&self, table_ncols: usize) -> usize {
+        self.inner.layout_width(table_ncols)
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        if self.opts.is_empty() || !self.inner.supports_ansi_styles() {
+            return self.inner.render_to(w, filled_table, widths);
+        }
+        let border = self.opts.border_color.map(|c| c.fg()).unwrap_or_default();
+        let has_header = filled_table.has_header();
+        let style_line: &LineStyle = &|row_idx, line| {
+            let style = self.row_style(row_idx, has_header);
+            Cow::from(self.style_line(line, &style, &border))
+        };
+        if border.is_empty() {
+            return self.inner.render_styled_to(
+                w,
+                filled_table,
+                widths,
+                style_line,
+            );
+        }
+        let mut w = EnclosingLinesWriter::new(w, &border, SGR_RESET);
+        self.inner.render_styled_to(
+            &mut w,
+            filled_table,
+            widths,
+            style_line,
+        )?;
+        w.finish()
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        self.inner.has_header_by_default()
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        self.inner.supports_ansi_styles()
+    }
+
+    fn is_markup(&self) -> bool {
+        self.inner.is_markup()
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.inner.set_alignments(alignments, vertical_alignments)
+    }
+
+    fn rewraps_text(&self) -> bool {
+        self.inner.rewraps_text()
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        let border = self.inner.titled_top_border(title, widths)?;
+        match self.opts.border_color {
+            Some(color) if self.inner.supports_ansi_styles() => {
+                Some(format!("{}{}{}", color.fg(), border, SGR_RESET))
+            }
+            _ => Some(border),
+        }
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        self.inner
+            .rendered_height(row_heights, has_header, has_footer)
+    }
+}
+
+/// A writer that encloses each line written to `inner` by `prefix` and
+/// `suffix`. Call [`finish`](EnclosingLinesWriter::finish) to close the last
+/// line if it doesn't end with a newline.
+struct EnclosingLinesWriter<'a> {
+    inner: &'a mut dyn Write,
+    prefix: &'a str,
+    suffix: &'a str,
+    at_line_start: bool,
+}
+
+impl<'a> EnclosingLinesWriter<'a> {
+    fn new(inner: &'a mut dyn Write, prefix: &'a str, suffix: &'a str) -> Self {
+        Self {
+            inner,
+            prefix,
+            suffix,
+            at_line_start: true,
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.at_line_start {
+            self.inner.write_all(self.suffix.as_bytes())?;
+            self.at_line_start = true;
+        }
+        Ok(())
+    }
+}
+
+impl Write for EnclosingLinesWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.at_line_start {
+                self.inner.write_all(self.prefix.as_bytes())?;
+                self.at_line_start = false;
+            }
+            match line.strip_suffix(b"\n") {
+                Some(line) => {
+                    self.inner.write_all(line)?;
+                    self.inner.write_all(self.suffix.as_bytes())?;
+                    self.inner.write_all(b"\n")?;
+                    self.at_line_start = true;
+                }
+                None => self.inner.write_all(line)?,
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::new_filled_table;
+    use crate::table_renderers::{Grid, Plain};
+
+    #[test]
+    fn test_styled_grid() {
+        let renderer = Styled::new(
+            Grid,
+            StyleOptions {
+                border_color: Some(Color::Red),
+                header_style: HeaderStyle::Bold,
+                zebra_color: None,
+            },
+        );
+        let s = renderer.render_table(&new_filled_table(3, 1, true), &[2]);
+        assert_eq!(
+            s,
+            "\x1b[31m+----+\x1b[0m\n\
+             \x1b[31m| \x1b[0m\x1b[1mab\x1b[0m\x1b[31m |\x1b[0m\n\
+             \x1b[31m+====+\x1b[0m\n\
+             \x1b[31m| \x1b[0mcd\x1b[0m\x1b[31m |\x1b[0m\n\
+             \x1b[31m+----+\x1b[0m\n\
+             \x1b[31m| \x1b[0mef\x1b[0m\x1b[31m |\x1b[0m\n\
+             \x1b[31m+----+\x1b[0m"
+        );
+        assert_eq!(renderer.layout_width(1), Grid.layout_width(1));
+    }
+
+    #[test]
+    fn test_styled_zebra() {
+        let renderer = Styled::new(
+            Plain,
+            StyleOptions {
+                header_style: HeaderStyle::Bold,
+                zebra_color: Some(Color::Blue),
+                ..Default::default()
+            },
+        );
+        let s = renderer.render_table(&new_filled_table(3, 1, false), &[2]);
+        assert_eq!(s, "ab\n\x1b[44mcd\x1b[0m\nef");
+        // The header is not a body row, even in `Plain`.
+        let s = renderer.render_table(&new_filled_table(3, 1, true), &[2]);
+        assert_eq!(s, "\x1b[1mab\x1b[0m\ncd\n\x1b[44mef\x1b[0m");
+    }
+
+    #[test]
+    fn test_enclosing_lines_writer() {
+        let mut buf = vec![];
+        let mut w = EnclosingLinesWriter::new(&mut buf, "<", ">");
+        w.write_all(b"ab\nc").unwrap();
+        w.write_all(b"d\n\ne").unwrap();
+        w.finish().unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "<ab>\n<cd>\n<>\n<e>");
+    }
+}
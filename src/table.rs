@@ -1,10 +1,18 @@
+use crate::ansi;
 use std::borrow::Cow;
+use std::io::{self, Write};
 
 /// A table of `T`.
 #[derive(Debug)]
 pub struct Table<T> {
     cells: Vec<T>,
     nrows: usize,
+    /// Whether the first row of the table is a header, or the first column
+    /// once the table is transposed.
+    has_header: bool,
+    /// Whether the last row of the table is a footer, or the last column once
+    /// the table is transposed.
+    has_footer: bool,
 }
 
 /// Indicate that a vec can not be interpreted as a `Table`, since its len
@@ -18,8 +26,13 @@ impl<T> Table<T> {
         cells: Vec<T>,
         nrows: usize,
     ) -> Result<Self, NotTableError> {
-        if cells.len() % nrows == 0 {
-            Ok(Self { cells, nrows })
+        if cells.len().is_multiple_of(nrows) {
+            Ok(Self {
+                cells,
+                nrows,
+                has_header: false,
+                has_footer: false,
+            })
         } else {
             Err(NotTableError)
         }
@@ -43,6 +56,53 @@ impl<T> Table<T> {
         self.cells.len() / self.nrows()
     }
 
+    /// Return `true` if the first row of the table is a header, or the first
+    /// column once the table is transposed.
+    #[inline]
+    pub const fn has_header(&self) -> bool {
+        self.has_header
+    }
+
+    /// Set whether the first row of the table is a header.
+    pub fn set_header(&mut self, has_header: bool) {
+        self.has_header = has_header;
+    }
+
+    /// Return `true` if the last row of the table is a footer, or the last
+    /// column once the table is transposed.
+    #[inline]
+    pub const fn has_footer(&self) -> bool {
+        self.has_footer
+    }
+
+    /// Set whether the last row of the table is a footer.
+    pub fn set_footer(&mut self, has_footer: bool) {
+        self.has_footer = has_footer;
+    }
+
+    /// Append `row` as the footer, which replaces the footer row if the table
+    /// has one already. Panics if `row` is not as long as the other rows.
+    pub fn set_footer_row(&mut self, row: Vec<T>) {
+        if self.has_footer() {
+            let ncols = self.ncols();
+            self.cells.truncate(self.cells.len() - ncols);
+            self.nrows -= 1;
+        }
+        self.insert_row(self.nrows(), row);
+        self.set_footer(true);
+    }
+
+    /// Insert `row` before the `row_idx`-th row. Panics if `row` is not as
+    /// long as the other rows, or if `row_idx` is out of range.
+    pub fn insert_row(&mut self, row_idx: usize, row: Vec<T>) {
+        let ncols = self.ncols();
+        assert_eq!(row.len(), ncols);
+        assert!(row_idx <= self.nrows());
+        let idx = row_idx * ncols;
+        self.cells.splice(idx..idx, row);
+        self.nrows += 1;
+    }
+
     /// Get all cells. Used in tests only.
     #[cfg(test)]
     pub(crate) fn cells(&self) -> &[T] {
@@ -86,7 +146,7 @@ impl<T> Table<T> {
             .zip(coordinates)
             .map(|(s, co)| CoordinateCell::new(s, co))
             .collect();
-        transposed_cells.sort_by(|a, b| a.coordinate.cmp(&b.coordinate));
+        transposed_cells.sort_by_key(|a| a.coordinate);
         let mut transposed_cells: Vec<_> =
             transposed_cells.drain(..).map(|x| x.into_cell()).collect();
         self.cells.append(&mut transposed_cells);
@@ -124,11 +184,73 @@ impl<T> Table<T> {
         let table = Table {
             nrows,
             cells: vec!["".to_string(); len],
+            has_header: false,
+            has_footer: false,
         };
         table.get_transpose_target_coordinates()
     }
 }
 
+impl<T: Clone> Table<T> {
+    /// Construct a new table from the rows at `row_indices`, in that order.
+    /// Panics if `row_indices` is empty or out of range. The new table has a
+    /// header (footer) if and only if this table has one, so the header row,
+    /// if any, should be selected first, and the footer row last.
+    pub fn select_rows(&self, row_indices: &[usize]) -> Table<T> {
+        assert!(!row_indices.is_empty());
+        let mut cells = Vec::with_capacity(row_indices.len() * self.ncols());
+        for i in row_indices.iter() {
+            cells.extend_from_slice(self.row(*i).unwrap());
+        }
+        let mut table = Table::from_vec(cells, row_indices.len()).unwrap();
+        table.set_header(self.has_header());
+        table.set_footer(self.has_footer());
+        table
+    }
+
+    /// Convert the table into records, i.e. a two-column table of key/value
+    /// pairs where the keys are the cells of the first row. Each of the other
+    /// rows becomes `ncols` consecutive rows. Return
+    /// [`EmptyTable`](crate::Error::EmptyTable) if there's no other row than
+    /// the first one.
+    pub fn to_records(&self) -> crate::Result<Table<T>> {
+        if self.nrows() < 2 {
+            return Err(crate::Error::EmptyTable);
+        }
+        let header = self.row(0).unwrap();
+        let mut cells = Vec::with_capacity(2 * (self.ncells() - header.len()));
+        for i in 1..self.nrows() {
+            for (key, value) in header.iter().zip(self.row(i).unwrap()) {
+                cells.push(key.clone());
+                cells.push(value.clone());
+            }
+        }
+        let nrows = cells.len() / 2;
+        Ok(Table::from_vec(cells, nrows).unwrap())
+    }
+}
+
+impl<T: Default> Table<T> {
+    /// Name the columns by `names`, which replace the cells of the header
+    /// row, or make up a new header row if the table has no header. In a new
+    /// header row, the columns beyond `names` are named by `T::default()`.
+    /// Names beyond the columns are ignored.
+    pub fn set_header_names(&mut self, names: Vec<T>) {
+        if !self.has_header() {
+            let ncols = self.ncols();
+            self.insert_row(
+                0,
+                std::iter::repeat_with(T::default).take(ncols).collect(),
+            );
+            self.set_header(true);
+        }
+        let header = self.row_mut(0).unwrap();
+        for (cell, name) in header.iter_mut().zip(names) {
+            *cell = name;
+        }
+    }
+}
+
 /// A cell with target coordinate, used to transpose a table.
 struct CoordinateCell<T> {
     cell: T,
@@ -145,6 +267,10 @@ impl<T> CoordinateCell<T> {
     }
 }
 
+/// Style a line of a cell given the index of its row, e.g. by enclosing it in
+/// ANSI escape sequences. See [`TableRenderer::render_styled_to`].
+pub type LineStyle<'a> = dyn Fn(usize, &str) -> Cow<'_, str> + 'a;
+
 pub trait TableRenderer {
     /// Return the part of table width attributed to the table layout rather
     /// than the table content, given the `ncols` of a table.
@@ -156,6 +282,124 @@ pub trait TableRenderer {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String;
+
+    /// Render a filled table into `w` given the widths of each column. The
+    /// output is the same as [`render_table`](TableRenderer::render_table),
+    /// but may be written row by row rather than as a whole.
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        w.write_all(self.render_table(filled_table, widths).as_bytes())
+    }
+
+    /// Render a filled table into `w` as [`render_to`](TableRenderer::render_to)
+    /// does, with every line of the cells in the `i`-th row replaced by
+    /// `style_line(i, line)`. The plaintext layouts style the rows as they
+    /// are drawn, whereas the default implementation renders a styled copy of
+    /// the table.
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
+        let nrows = filled_table.nrows();
+        let mut styled_cells = Vec::with_capacity(filled_table.ncells());
+        for i in 0..nrows {
+            for cell in filled_table.row(i).unwrap() {
+                let styled_cell: Vec<Cow<str>> =
+                    cell.iter().map(|line| style_line(i, line)).collect();
+                styled_cells.push(styled_cell);
+            }
+        }
+        let mut styled_table = Table::from_vec(styled_cells, nrows).unwrap();
+        styled_table.set_header(filled_table.has_header());
+        styled_table.set_footer(filled_table.has_footer());
+        self.render_to(w, &styled_table, widths)
+    }
+
+    /// Return `true` if the first row of a table is taken as a header unless
+    /// specified otherwise by [`Table::set_header`]. Either way, the header is
+    /// rendered as told by [`Table::has_header`].
+    fn has_header_by_default(&self) -> bool {
+        false
+    }
+
+    /// Return `true` if the rendered table may be styled by ANSI escape
+    /// sequences, which is the case for plaintext layouts.
+    fn supports_ansi_styles(&self) -> bool {
+        true
+    }
+
+    /// Return `true` if the table is written in a markup language, e.g. HTML,
+    /// which lays out the cells by itself. The cells of such a table needn't
+    /// be filled by [`fill_table`].
+    fn is_markup(&self) -> bool {
+        false
+    }
+
+    /// Align the text of the `j`-th column by `alignments[j]` and
+    /// `vertical_alignments[j]` in the markup layouts, which lay out the
+    /// cells by themselves. The other layouts ignore the alignments, since
+    /// their cells are aligned by [`fill_table`].
+    fn set_alignments(
+        &mut self,
+        _alignments: &[Alignment],
+        _vertical_alignments: &[VerticalAlignment],
+    ) {
+    }
+
+    /// Return `true` if the wrapped lines of each cell are joined and wrapped
+    /// again by the layout, e.g. in LaTeX `p` columns. The words broken by
+    /// the wrapping should then be joined back by [`rejoin_broken_words`].
+    fn rewraps_text(&self) -> bool {
+        false
+    }
+
+    /// Return the top border of the table given the widths of each column,
+    /// with `title` drawn inside it, e.g. `┌─ Title ───┐`, or `None` if the
+    /// layout has no such border or `title` can't fit in it.
+    fn titled_top_border(
+        &self,
+        _title: &str,
+        _widths: &[usize],
+    ) -> Option<String> {
+        None
+    }
+
+    /// Render a filled table into `w` as [`render_to`](TableRenderer::render_to)
+    /// does, with `title` and `caption` made into the caption of the table in
+    /// markup, e.g. `<caption>` in HTML. The default implementation leaves
+    /// them out, since the other layouts are titled by
+    /// [`Titled`](crate::titles::Titled).
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        _title: Option<&str>,
+        _caption: Option<&str>,
+    ) -> io::Result<()> {
+        self.render_to(w, filled_table, widths)
+    }
+
+    /// Return the number of lines of the rendered table given the number of
+    /// lines of each row and whether the first row is a header and the last
+    /// row a footer, or `None` if the rendered table isn't meant to be read
+    /// line by line, e.g. in markup languages. Used to paginate the rows by
+    /// [`paginate_rows`].
+    fn rendered_height(
+        &self,
+        _row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        None
+    }
 }
 
 impl TableRenderer for Box<dyn TableRenderer> {
@@ -170,16 +414,205 @@ impl TableRenderer for Box<dyn TableRenderer> {
     ) -> String {
         self.as_ref().render_table(filled_table, widths)
     }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.as_ref().render_to(w, filled_table, widths)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
+        self.as_ref()
+            .render_styled_to(w, filled_table, widths, style_line)
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        self.as_ref().has_header_by_default()
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        self.as_ref().supports_ansi_styles()
+    }
+
+    fn is_markup(&self) -> bool {
+        self.as_ref().is_markup()
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.as_mut().set_alignments(alignments, vertical_alignments)
+    }
+
+    fn rewraps_text(&self) -> bool {
+        self.as_ref().rewraps_text()
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        self.as_ref().titled_top_border(title, widths)
+    }
+
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        title: Option<&str>,
+        caption: Option<&str>,
+    ) -> io::Result<()> {
+        self.as_ref()
+            .render_titled_to(w, filled_table, widths, title, caption)
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        self.as_ref()
+            .rendered_height(row_heights, has_header, has_footer)
+    }
 }
 
+/// Split the rows of a table into pages, each rendered in at most
+/// `page_height` lines by `table_renderer` given the number of lines of each
+/// row. The header row, if `has_header`, is repeated at the beginning of
+/// every page, while the footer row, if `has_footer`, ends the last page only.
+/// A row is never split across pages. A row taller than a page by itself
+/// makes up a page of its own. Return the indices of the rows on each page,
+/// or `None` if the layout can't be paginated.
+pub fn paginate_rows(
+    table_renderer: &dyn TableRenderer,
+    row_heights: &[usize],
+    has_header: bool,
+    has_footer: bool,
+    page_height: usize,
+) -> Option<Vec<Vec<usize>>> {
+    table_renderer.rendered_height(row_heights, has_header, has_footer)?;
+    let header_len = if has_header && !row_heights.is_empty() {
+        1
+    } else {
+        0
+    };
+    let mut pages = vec![];
+    let mut page: Vec<usize> = (0..header_len).collect();
+    let mut page_heights: Vec<usize> = row_heights[..header_len].to_vec();
+    for (i, h) in row_heights.iter().enumerate().skip(header_len) {
+        page_heights.push(*h);
+        let is_footer = has_footer && i == row_heights.len() - 1;
+        let height = table_renderer
+            .rendered_height(&page_heights, has_header, is_footer)
+            .unwrap();
+        if height > page_height && page.len() > header_len {
+            pages.push(page);
+            page = (0..header_len).collect();
+            page_heights.truncate(header_len);
+            page_heights.push(*h);
+        }
+        page.push(i);
+    }
+    if page.len() > header_len || pages.is_empty() {
+        pages.push(page);
+    }
+    Some(pages)
+}
+
+/// Render a filled table into string by [`TableRenderer::render_to`], for
+/// renderers that write the table row by row.
+pub fn render_to_string<R: TableRenderer + ?Sized>(
+    renderer: &R,
+    filled_table: &Table<Vec<Cow<'_, str>>>,
+    widths: &[usize],
+) -> String {
+    let mut buf = vec![];
+    // Writing to a `Vec` never fails.
+    renderer.render_to(&mut buf, filled_table, widths).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Build a filled table of `nrows` by `ncols` single-line cells `"ab"`, `"cd"`,
+/// `"ef"` and so on in row-major order, with a header if `has_header`. Used in
+/// tests only.
+#[cfg(test)]
+pub(crate) fn new_filled_table(
+    nrows: usize,
+    ncols: usize,
+    has_header: bool,
+) -> Table<Vec<Cow<'static, str>>> {
+    let cells = (0..nrows * ncols)
+        .map(|k| {
+            let a = b'a' + 2 * k as u8;
+            vec![Cow::from(String::from_utf8(vec![a, a + 1]).unwrap())]
+        })
+        .collect();
+    let mut table = Table::from_vec(cells, nrows).unwrap();
+    table.set_header(has_header);
+    table
+}
+
+/// The default marker of truncated cells.
+pub const DEFAULT_ELLIPSIS: &str = "…";
+
 /// A wrapper over [`textwrap::Options`] that can change its `width` as needed.
+/// It also holds the options of the columns and the header wrapped
+/// differently from the rest, the maximum number of lines of the cells in
+/// each column, and the columns in which long words are broken regardless of
+/// [`textwrap::Options::break_words`].
+#[derive(Clone)]
 pub struct WrapOptionsVarWidths<'a> {
     inner: textwrap::Options<'a>,
+    /// The `break_words` of `inner` as constructed.
+    default_break_words: bool,
+    /// The options of each column, `None` if the column follows `inner`.
+    columns: Vec<Option<ColumnOptions<'a>>>,
+    /// The options of the header row, `None` if the header is wrapped as the
+    /// other rows of its column.
+    header: Option<ColumnOptions<'a>>,
+    /// Whether to break long words in each column, `false` if the column
+    /// follows `default_break_words`.
+    break_words: Vec<bool>,
+    /// The maximum number of lines of each column, `None` if unlimited.
+    max_lines: Vec<Option<usize>>,
+    /// The marker appended to the last line kept in a truncated cell.
+    ellipsis: String,
+}
+
+/// The options of a column that doesn't follow the options shared by the
+/// other columns.
+#[derive(Clone)]
+struct ColumnOptions<'a> {
+    inner: textwrap::Options<'a>,
+    /// The `break_words` of `inner` as constructed.
+    default_break_words: bool,
 }
 
 impl<'a> From<textwrap::Options<'a>> for WrapOptionsVarWidths<'a> {
     fn from(value: textwrap::Options<'a>) -> Self {
-        Self { inner: value }
+        Self {
+            default_break_words: value.break_words,
+            inner: value,
+            columns: vec![],
+            header: None,
+            break_words: vec![],
+            max_lines: vec![],
+            ellipsis: DEFAULT_ELLIPSIS.into(),
+        }
     }
 }
 
@@ -200,19 +633,231 @@ impl<'a> WrapOptionsVarWidths<'a> {
     /// set to `width`.
     pub fn as_width(&mut self, width: usize) -> &textwrap::Options<'a> {
         self.inner.width = width;
+        self.inner.break_words = self.default_break_words;
         &self.inner
     }
+
+    /// Like [`as_width`](WrapOptionsVarWidths::as_width), but for the
+    /// `col_idx`-th column, whose long words may be broken.
+    pub fn as_column_width(
+        &mut self,
+        col_idx: usize,
+        width: usize,
+    ) -> &textwrap::Options<'a> {
+        let break_words = self.break_words_of(col_idx);
+        self.as_column_width_breaking(col_idx, width, break_words)
+    }
+
+    /// Like [`as_column_width`](WrapOptionsVarWidths::as_column_width), but
+    /// break long words if and only if `break_words` is `true`.
+    fn as_column_width_breaking(
+        &mut self,
+        col_idx: usize,
+        width: usize,
+        break_words: bool,
+    ) -> &textwrap::Options<'a> {
+        let inner = match self.columns.get_mut(col_idx) {
+            Some(Some(column)) => &mut column.inner,
+            _ => &mut self.inner,
+        };
+        inner.width = width;
+        inner.break_words = break_words;
+        inner
+    }
+
+    /// Like [`as_column_width`](WrapOptionsVarWidths::as_column_width), but
+    /// for a cell of the header row if `is_header`. The header options, if
+    /// any, take precedence over the column options, and the long words in
+    /// the header are broken only if the header options say so.
+    pub fn as_cell_width(
+        &mut self,
+        is_header: bool,
+        col_idx: usize,
+        width: usize,
+    ) -> &textwrap::Options<'a> {
+        if !is_header || self.header.is_none() {
+            return self.as_column_width(col_idx, width);
+        }
+        let header = self.header.as_mut().unwrap();
+        header.inner.width = width;
+        header.inner.break_words = header.default_break_words;
+        &header.inner
+    }
+
+    /// Get whether to break long words in the `col_idx`-th column.
+    pub fn break_words_of(&self, col_idx: usize) -> bool {
+        self.default_break_words_of(col_idx)
+            || self.break_words.get(col_idx).copied().unwrap_or(false)
+    }
+
+    /// Get whether to break long words in the `col_idx`-th column as
+    /// constructed.
+    fn default_break_words_of(&self, col_idx: usize) -> bool {
+        match self.columns.get(col_idx) {
+            Some(Some(column)) => column.default_break_words,
+            _ => self.default_break_words,
+        }
+    }
+
+    /// Break long words in the `col_idx`-th column.
+    pub fn set_break_words(&mut self, col_idx: usize) {
+        if self.break_words.len() <= col_idx {
+            self.break_words.resize(col_idx + 1, false);
+        }
+        self.break_words[col_idx] = true;
+    }
+
+    /// Set the maximum number of lines of each column. The cells with more
+    /// lines are truncated. Columns beyond `max_lines` are unlimited.
+    pub fn max_lines(mut self, max_lines: Vec<Option<usize>>) -> Self {
+        debug_assert!(!max_lines.contains(&Some(0)));
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Set the marker of truncated cells, default to [`DEFAULT_ELLIPSIS`].
+    pub fn ellipsis(mut self, ellipsis: String) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Set the algorithm to wrap the words into lines.
+    pub fn wrap_algorithm(
+        mut self,
+        wrap_algorithm: textwrap::WrapAlgorithm,
+    ) -> Self {
+        self.inner.wrap_algorithm = wrap_algorithm;
+        self
+    }
+
+    /// Set how to find the words in a line of text.
+    pub fn word_separator(
+        mut self,
+        word_separator: textwrap::WordSeparator,
+    ) -> Self {
+        self.inner.word_separator = word_separator;
+        self
+    }
+
+    /// Set how to split the words into smaller pieces, e.g. by hyphenation.
+    pub fn word_splitter(
+        mut self,
+        word_splitter: textwrap::WordSplitter,
+    ) -> Self {
+        self.inner.word_splitter = word_splitter;
+        self
+    }
+
+    /// Set the options of each column, `None` if the column follows the
+    /// shared options. The `width` of the options is erased, and the options
+    /// of a column are not affected by the shared options set afterwards.
+    /// Columns beyond `column_options` follow the shared options.
+    pub fn column_options(
+        mut self,
+        column_options: Vec<Option<textwrap::Options<'a>>>,
+    ) -> Self {
+        self.columns = column_options
+            .into_iter()
+            .map(|inner| {
+                inner.map(|inner| ColumnOptions {
+                    default_break_words: inner.break_words,
+                    inner,
+                })
+            })
+            .collect();
+        self
+    }
+
+    /// Set the options of the header row, regardless of the column options.
+    /// The `width` of the options is erased.
+    pub fn header_options(mut self, header: textwrap::Options<'a>) -> Self {
+        self.header = Some(ColumnOptions {
+            default_break_words: header.break_words,
+            inner: header,
+        });
+        self
+    }
+
+    /// Get the options shared by the columns without options of their own.
+    pub fn shared_options(&self) -> &textwrap::Options<'a> {
+        &self.inner
+    }
+
+    /// Get the maximum number of lines of the `col_idx`-th column.
+    pub fn max_lines_of(&self, col_idx: usize) -> Option<usize> {
+        self.max_lines.get(col_idx).copied().flatten()
+    }
+
+    /// Construct new options for the table made up of the columns at
+    /// `col_indices`, in that order.
+    pub fn select_columns(&self, col_indices: &[usize]) -> Self {
+        let max_lines =
+            col_indices.iter().map(|j| self.max_lines_of(*j)).collect();
+        let break_words = col_indices
+            .iter()
+            .map(|j| self.break_words.get(*j).copied().unwrap_or(false))
+            .collect();
+        let columns = col_indices
+            .iter()
+            .map(|j| self.columns.get(*j).cloned().flatten())
+            .collect();
+        Self {
+            columns,
+            break_words,
+            max_lines,
+            ..self.clone()
+        }
+    }
+}
+
+/// Truncate wrapped `lines` to `max_lines`, and end the last line kept with
+/// `ellipsis` within `width`.
+fn truncate_lines(
+    lines: &mut Vec<Cow<'_, str>>,
+    max_lines: usize,
+    width: usize,
+    ellipsis: &str,
+) {
+    lines.truncate(max_lines);
+    let Some(last_line) = lines.last_mut() else {
+        return;
+    };
+    let ellipsis_width = textwrap::core::display_width(ellipsis);
+    if ellipsis_width > width {
+        *last_line =
+            Cow::from(ansi::truncate_to_width(last_line, width).to_owned());
+    } else {
+        let kept = ansi::truncate_to_width(last_line, width - ellipsis_width);
+        *last_line = Cow::from(format!("{}{}", kept.trim_end(), ellipsis));
+    }
 }
 
-/// Wrap a row of strings. Return the wrapped lines of each cell along the row.
+/// Wrap a row of strings, which is the header row if `is_header`. Return the
+/// wrapped lines of each cell along the row. Cells longer than the maximum
+/// number of lines of their columns are truncated. Colors and other styles of
+/// the text are closed at the end of each line and reopened at the beginning
+/// of the next one.
 fn wrap_row<'s>(
     row: &'s [String],
     widths: &[usize],
     opts: &mut WrapOptionsVarWidths<'_>,
+    is_header: bool,
 ) -> Vec<Vec<Cow<'s, str>>> {
     row.iter()
         .zip(widths.iter())
-        .map(|(s, w)| textwrap::wrap(s, opts.as_width(*w)))
+        .enumerate()
+        .map(|(j, (s, w))| {
+            let max_lines = opts.max_lines_of(j);
+            let mut lines =
+                textwrap::wrap(s, opts.as_cell_width(is_header, j, *w));
+            if let Some(max_lines) = max_lines {
+                if lines.len() > max_lines {
+                    truncate_lines(&mut lines, max_lines, *w, &opts.ellipsis);
+                }
+            }
+            ansi::carry_styles_across_lines(&mut lines);
+            lines
+        })
         .collect()
 }
 
@@ -225,40 +870,270 @@ pub fn wrap_table<'s>(
     let nrows = table.nrows();
     let mut wrapped_cells = Vec::with_capacity(table.ncells());
     for i in 0..nrows {
-        wrapped_cells.extend(wrap_row(table.row(i).unwrap(), widths, opts));
+        let is_header = i == 0 && table.has_header();
+        wrapped_cells.extend(wrap_row(
+            table.row(i).unwrap(),
+            widths,
+            opts,
+            is_header,
+        ));
+    }
+    let mut wrapped_table = Table::from_vec(wrapped_cells, nrows).unwrap();
+    wrapped_table.set_header(table.has_header());
+    wrapped_table.set_footer(table.has_footer());
+    wrapped_table
+}
+
+/// Horizontal alignment of the text in a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+    /// Line up the decimal points of the numbers along the column, as far as
+    /// the column width allows. Lines that are not numbers are right-aligned.
+    Decimal,
+}
+
+/// Vertical alignment of the text in a column, with respect to the tallest
+/// cell in the same row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// If `line` is a number, return the display width of its integer part, i.e.
+/// the part of the mantissa before the decimal point. The whitespace around
+/// the number doesn't count.
+fn decimal_integer_width(line: &str) -> Option<usize> {
+    let text = ansi::strip_sgr(line);
+    let text = text.trim();
+    // `inf`, `NaN` and the like are not taken as numbers.
+    if !text.contains(|c: char| c.is_ascii_digit())
+        || text.parse::<f64>().is_err()
+    {
+        return None;
     }
-    Table::from_vec(wrapped_cells, nrows).unwrap()
+    let integer_part = text.split(['.', 'e', 'E']).next().unwrap();
+    Some(textwrap::core::display_width(integer_part))
+}
+
+/// If `line` is a number, return the display width of the rest of it after
+/// its integer part, i.e. the decimal point, the fractional part and the
+/// exponent. The whitespace around the number doesn't count.
+pub(crate) fn decimal_fraction_width(line: &str) -> Option<usize> {
+    let text = ansi::strip_sgr(line);
+    let text = text.trim();
+    decimal_integer_width(text)
+        .map(|w| textwrap::core::display_width(text) - w)
+}
+
+/// The max display width of the integer parts of the numbers in the wrapped
+/// cells of a column. See [`Alignment::Decimal`].
+fn max_decimal_integer_width<'a, I>(wrapped_col: I) -> usize
+where
+    I: Iterator<Item = &'a Vec<Cow<'a, str>>>,
+{
+    wrapped_col
+        .flat_map(|cell| cell.iter())
+        .filter_map(|line| decimal_integer_width(line))
+        .max()
+        .unwrap_or(0)
 }
 
 /// Fill a wrapped cell. `max_nlines` is the max number of lines of cells of
-/// the row where current cell lies in.
+/// the row where current cell lies in. `decimal_integer_width` is used only
+/// if `alignment` is [`Alignment::Decimal`], and should be the value returned
+/// by [`max_decimal_integer_width`] on the column.
 fn fill_cell(
     wrapped_cell: &mut Vec<Cow<'_, str>>,
     width: usize,
     max_nlines: usize,
+    alignment: Alignment,
+    vertical_alignment: VerticalAlignment,
+    decimal_integer_width: usize,
 ) {
     for line in wrapped_cell.iter_mut() {
-        let padded = " "
-            .repeat(width.saturating_sub(textwrap::core::display_width(line)));
+        // The whitespace before a number would put it off the decimal point.
+        if alignment == Alignment::Decimal
+            && line.starts_with(char::is_whitespace)
+            && self::decimal_integer_width(line).is_some()
+        {
+            match line {
+                Cow::Borrowed(s) => *s = s.trim_start(),
+                Cow::Owned(s) => {
+                    s.drain(..s.len() - s.trim_start().len());
+                }
+            }
+        }
+        let line_width = textwrap::core::display_width(line);
+        let padding = width.saturating_sub(line_width);
+        let left_padding = match alignment {
+            Alignment::Left => 0,
+            Alignment::Right => padding,
+            Alignment::Center => padding / 2,
+            Alignment::Decimal => match self::decimal_integer_width(line) {
+                None => padding,
+                Some(w) => std::cmp::min(decimal_integer_width - w, padding),
+            },
+        };
+        if left_padding > 0 {
+            line.to_mut().insert_str(0, &" ".repeat(left_padding));
+        }
+        let padded = " ".repeat(padding - left_padding);
         line.to_mut().push_str(&padded);
     }
-    let nlines = wrapped_cell.len();
-    for _ in 0..max_nlines.saturating_sub(nlines) {
-        let padded = " ".repeat(width);
-        wrapped_cell.push(Cow::from(padded));
-    }
+    let nblanks = max_nlines.saturating_sub(wrapped_cell.len());
+    let nblanks_above = match vertical_alignment {
+        VerticalAlignment::Top => 0,
+        VerticalAlignment::Middle => nblanks / 2,
+        VerticalAlignment::Bottom => nblanks,
+    };
+    let blank = Cow::from(" ".repeat(width));
+    wrapped_cell
+        .splice(0..0, std::iter::repeat_n(blank.clone(), nblanks_above));
+    wrapped_cell.extend(std::iter::repeat_n(blank, nblanks - nblanks_above));
 }
 
-/// Fill the wrapped table, assuming the table is non-empty.
-pub fn fill_table(table: &mut Table<Vec<Cow<'_, str>>>, widths: &[usize]) {
+/// Fill the wrapped table, assuming the table is non-empty. The `widths`,
+/// `alignments` and `vertical_alignments` are those of each column.
+pub fn fill_table(
+    table: &mut Table<Vec<Cow<'_, str>>>,
+    widths: &[usize],
+    alignments: &[Alignment],
+    vertical_alignments: &[VerticalAlignment],
+) {
     let nrows = table.nrows();
+    let ncols = table.ncols();
+    assert_eq!(alignments.len(), ncols);
+    assert_eq!(vertical_alignments.len(), ncols);
+    let decimal_integer_widths: Vec<usize> = alignments
+        .iter()
+        .enumerate()
+        .map(|(j, alignment)| match alignment {
+            Alignment::Decimal => max_decimal_integer_width(
+                (0..nrows).map(|i| table.get(i, j).unwrap()),
+            ),
+            _ => 0,
+        })
+        .collect();
     for i in 0..nrows {
         let wrapped_row = table.row_mut(i).unwrap();
         let max_nlines = wrapped_row.iter().map(|r| r.len()).max().unwrap();
-        for (wrapped_cell, w) in wrapped_row.iter_mut().zip(widths.iter()) {
-            fill_cell(wrapped_cell, *w, max_nlines);
+        for (j, (wrapped_cell, w)) in
+            wrapped_row.iter_mut().zip(widths.iter()).enumerate()
+        {
+            fill_cell(
+                wrapped_cell,
+                *w,
+                max_nlines,
+                alignments[j],
+                vertical_alignments[j],
+                decimal_integer_widths[j],
+            );
+        }
+    }
+}
+
+/// Find the cells along a row, which is the header row if `is_header`, whose
+/// words are broken since they are longer than the corresponding width in
+/// `widths`, and only because their columns break long words. Return the
+/// column indices of the cells.
+pub fn find_broken_words(
+    row: &[String],
+    widths: &[usize],
+    opts: &mut WrapOptionsVarWidths<'_>,
+    is_header: bool,
+) -> Vec<usize> {
+    let mut col_indices = vec![];
+    if is_header && opts.header.is_some() {
+        return col_indices;
+    }
+    for (col_idx, (s, w)) in row.iter().zip(widths.iter()).enumerate() {
+        if opts.break_words_of(col_idx) && !opts.default_break_words_of(col_idx)
+        {
+            let lines = textwrap::wrap(
+                s,
+                opts.as_column_width_breaking(col_idx, *w, false),
+            );
+            if lines
+                .iter()
+                .any(|line| textwrap::core::display_width(line) > *w)
+            {
+                col_indices.push(col_idx);
+            }
         }
     }
+    col_indices
+}
+
+/// Join back the lines of each wrapped cell in `wrapped_table` that end in the
+/// middle of a word of the corresponding cell in `table`, which the wrapped
+/// table is wrapped from. The hyphens inserted at the breaks are removed.
+pub fn rejoin_broken_words<'s>(
+    wrapped_table: &mut Table<Vec<Cow<'s, str>>>,
+    table: &Table<String>,
+) {
+    for (cell, source) in wrapped_table.cells.iter_mut().zip(&table.cells) {
+        let lines = std::mem::take(cell);
+        *cell = rejoin_broken_words_of_cell(lines, source);
+    }
+}
+
+fn rejoin_broken_words_of_cell<'s>(
+    lines: Vec<Cow<'s, str>>,
+    source: &str,
+) -> Vec<Cow<'s, str>> {
+    // The lines are matched against `source` without their styles, which are
+    // reopened on every line by `ansi::carry_styles_across_lines`.
+    let source = ansi::strip_sgr(source);
+    let mut rejoined: Vec<Cow<'s, str>> = Vec::with_capacity(lines.len());
+    // Where the last line ends in `source`. The lines follow one another in
+    // `source`, apart from the whitespace dropped between them by the
+    // wrapping, so each line is matched right there rather than searched for,
+    // which may find the same words further on.
+    let mut pos = 0;
+    let mut prev_broken = false;
+    for mut line in lines {
+        let stripped = ansi::strip_sgr(&line);
+        // The indent of the line is not in `source`.
+        let text = stripped.trim_start();
+        let start = source.len() - source[pos..].trim_start().len();
+        let rest = &source[start..];
+        let (end, hyphen_inserted) = if rest.starts_with(text) {
+            (Some(start + text.len()), false)
+        } else {
+            match text.strip_suffix('-') {
+                Some(text) if rest.starts_with(text) => {
+                    (Some(start + text.len()), true)
+                }
+                _ => (None, false),
+            }
+        };
+        let broken = end.is_some_and(|end| {
+            source[end..].starts_with(|c: char| !c.is_whitespace())
+        });
+        if let Some(end) = end {
+            pos = end;
+        }
+        if hyphen_inserted && broken {
+            // The hyphen may be followed by a reset of the styles.
+            let i = line.rfind('-').unwrap();
+            line.to_mut().remove(i);
+        }
+        match rejoined.last_mut() {
+            Some(prev) if prev_broken => {
+                prev.to_mut().push_str(&ansi::trim_start(&line))
+            }
+            _ => rejoined.push(line),
+        }
+        prev_broken = broken;
+    }
+    rejoined
 }
 
 /// Ensure all lines in a wrapped row is within corresponding width in
@@ -272,9 +1147,7 @@ pub fn ensure_row_within_widths(
         wrapped_row.iter().zip(widths.iter()).enumerate()
     {
         if cell.iter().any(|s| textwrap::core::display_width(s) > *w) {
-            return Err(crate::Error::ColumnNotWideEnough(Some((
-                row_idx, col_idx,
-            ))));
+            return Err(crate::Error::ColumnNotWideEnough(row_idx, col_idx));
         }
     }
     Ok(())
@@ -316,6 +1189,8 @@ mod tests {
                 .into_iter()
                 .map(|s| s.to_string())
                 .collect(),
+            has_header: false,
+            has_footer: false,
         };
         table.transpose();
 
@@ -333,21 +1208,427 @@ mod tests {
         assert_eq!(table.nrows, 3);
     }
 
+    #[test]
+    fn test_paginate_rows() {
+        use crate::table_renderers::{Grid, Html, Plain, Simple};
+
+        let row_heights = [1, 2, 3, 1, 5];
+        assert_eq!(
+            paginate_rows(&Grid, &row_heights, true, false, 8),
+            Some(vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![0, 4]])
+        );
+        assert_eq!(
+            paginate_rows(&Plain, &row_heights, false, false, 4),
+            Some(vec![vec![0, 1], vec![2, 3], vec![4]])
+        );
+        assert_eq!(
+            paginate_rows(
+                &Html::default(),
+                &row_heights,
+                true,
+                false,
+                4
+            ),
+            None
+        );
+        // The rule above the footer pushes it to a page of its own.
+        assert_eq!(
+            paginate_rows(&Simple, &[1, 1, 1, 1], true, true, 4),
+            Some(vec![vec![0, 1, 2], vec![0, 3]])
+        );
+    }
+
+    #[test]
+    fn test_table_select_rows() {
+        let table =
+            Table::from_vec(vec!["a", "b", "c", "d", "e", "f"], 3).unwrap();
+        let selected = table.select_rows(&[2, 0]);
+        assert_eq!(selected.cells, vec!["e", "f", "a", "b"]);
+        assert_eq!(selected.nrows, 2);
+    }
+
+    #[test]
+    fn test_table_set_footer_row() {
+        let mut table = Table::from_vec(vec!["a", "b", "c", "d"], 2).unwrap();
+        table.set_footer_row(vec!["x", "y"]);
+        assert!(table.has_footer());
+        assert_eq!(table.cells, vec!["a", "b", "c", "d", "x", "y"]);
+        table.set_footer_row(vec!["z", "w"]);
+        assert_eq!(table.cells, vec!["a", "b", "c", "d", "z", "w"]);
+        assert_eq!(table.nrows, 3);
+    }
+
+    #[test]
+    fn test_table_set_header_names() {
+        let mut table = Table::from_vec(vec!["a", "b", "c", "d"], 2).unwrap();
+        table.set_header_names(vec!["x"]);
+        assert!(table.has_header());
+        assert_eq!(table.cells, vec!["x", "", "a", "b", "c", "d"]);
+        table.set_header_names(vec!["y", "z", "w"]);
+        assert_eq!(table.cells, vec!["y", "z", "a", "b", "c", "d"]);
+        assert_eq!(table.nrows, 3);
+        table.transpose();
+        assert!(table.select_rows(&[1]).has_header());
+    }
+
+    #[test]
+    fn test_table_to_records() {
+        let table =
+            Table::from_vec(vec!["k1", "k2", "a", "b", "c", "d"], 3).unwrap();
+        let records = table.to_records().unwrap();
+        assert_eq!(
+            records.cells,
+            vec!["k1", "a", "k2", "b", "k1", "c", "k2", "d"]
+        );
+        assert_eq!(records.nrows, 4);
+        let table = Table::from_vec(vec!["k1", "k2"], 1).unwrap();
+        assert!(table.to_records().is_err());
+    }
+
     #[test]
     fn test_fill_cell() {
         let mut cell = vec![Cow::from("abcde")];
-        fill_cell(&mut cell, 10, 2);
+        fill_cell(&mut cell, 10, 2, Alignment::Left, VerticalAlignment::Top, 0);
         assert_eq!(
             cell,
             vec![Cow::from("abcde     "), Cow::from("          ")]
         );
 
         let mut cell = vec![Cow::from("12345678")];
-        fill_cell(&mut cell, 5, 1);
+        fill_cell(&mut cell, 5, 1, Alignment::Left, VerticalAlignment::Top, 0);
         assert_eq!(cell, vec![Cow::from("12345678")]);
 
         let mut cell = vec![Cow::from("12345678")];
-        fill_cell(&mut cell, 5, 2);
+        fill_cell(&mut cell, 5, 2, Alignment::Left, VerticalAlignment::Top, 0);
         assert_eq!(cell, vec![Cow::from("12345678"), Cow::from("     ")]);
+
+        let mut cell = vec![Cow::from("abcde")];
+        fill_cell(
+            &mut cell,
+            10,
+            1,
+            Alignment::Right,
+            VerticalAlignment::Top,
+            0,
+        );
+        assert_eq!(cell, vec![Cow::from("     abcde")]);
+
+        let mut cell = vec![Cow::from("abcde")];
+        fill_cell(
+            &mut cell,
+            10,
+            1,
+            Alignment::Center,
+            VerticalAlignment::Top,
+            0,
+        );
+        assert_eq!(cell, vec![Cow::from("  abcde   ")]);
+    }
+
+    #[test]
+    fn test_fill_table_vertical_alignment() {
+        let cells = ["a", "b", "c", "d\ne\nf\ng"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let table = Table::from_vec(cells, 1).unwrap();
+        let mut opts = WrapOptionsVarWidths::default();
+        let widths = [1, 1, 1, 1];
+        let mut wrapped_table = wrap_table(&table, &widths, &mut opts);
+        fill_table(
+            &mut wrapped_table,
+            &widths,
+            &[Alignment::Left; 4],
+            &[
+                VerticalAlignment::Top,
+                VerticalAlignment::Middle,
+                VerticalAlignment::Bottom,
+                VerticalAlignment::Middle,
+            ],
+        );
+        let blank = Cow::from(" ");
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![
+                    Cow::from("a"),
+                    blank.clone(),
+                    blank.clone(),
+                    blank.clone()
+                ],
+                vec![
+                    blank.clone(),
+                    Cow::from("b"),
+                    blank.clone(),
+                    blank.clone()
+                ],
+                vec![
+                    blank.clone(),
+                    blank.clone(),
+                    blank.clone(),
+                    Cow::from("c")
+                ],
+                vec![
+                    Cow::from("d"),
+                    Cow::from("e"),
+                    Cow::from("f"),
+                    Cow::from("g")
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_table_colored() {
+        let cells = vec!["\x1b[32mfoo bar\x1b[0m baz".to_string()];
+        let table = Table::from_vec(cells, 1).unwrap();
+        let mut opts = WrapOptionsVarWidths::default();
+        let widths = [3];
+        let mut wrapped_table = wrap_table(&table, &widths, &mut opts);
+        ensure_row_within_widths(0, wrapped_table.row(0).unwrap(), &widths)
+            .unwrap();
+        fill_table(
+            &mut wrapped_table,
+            &widths,
+            &[Alignment::Right],
+            &[VerticalAlignment::Top],
+        );
+        assert_eq!(
+            wrapped_table.cells,
+            vec![vec![
+                Cow::from("\x1b[32mfoo\x1b[0m"),
+                Cow::from("\x1b[32mbar\x1b[0m"),
+                Cow::from("baz"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_wrap_table_max_lines() {
+        let cells = vec![
+            "\x1b[32mfoo bar\x1b[0m baz".to_string(),
+            "foo bar baz".to_string(),
+        ];
+        let table = Table::from_vec(cells, 1).unwrap();
+        let mut opts =
+            WrapOptionsVarWidths::default().max_lines(vec![Some(2), Some(1)]);
+        let widths = [3, 5];
+        let wrapped_table = wrap_table(&table, &widths, &mut opts);
+        ensure_row_within_widths(0, wrapped_table.row(0).unwrap(), &widths)
+            .unwrap();
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![
+                    Cow::from("\x1b[32mfoo\x1b[0m"),
+                    Cow::from("\x1b[32mba…\x1b[0m"),
+                ],
+                vec![Cow::from("foo…")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_table_header_options() {
+        let cells = vec!["abcdef".to_string(), "abcdef".to_string()];
+        let mut table = Table::from_vec(cells, 2).unwrap();
+        table.set_header(true);
+        let mut opts = WrapOptionsVarWidths::default()
+            .header_options(textwrap::Options::new(79).break_words(false));
+        opts.set_break_words(0);
+        let wrapped_table = wrap_table(&table, &[3], &mut opts);
+        assert!(wrapped_table.has_header());
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![Cow::from("abcdef")],
+                vec![Cow::from("abc"), Cow::from("def")],
+            ]
+        );
+        let row = table.row(0).unwrap();
+        assert!(find_broken_words(row, &[3], &mut opts, true).is_empty());
+        assert_eq!(find_broken_words(row, &[3], &mut opts, false), vec![0]);
+    }
+
+    #[test]
+    fn test_wrap_table_column_options() {
+        let cells = vec!["foo-bar baz".to_string(), "foo-bar baz".to_string()];
+        let table = Table::from_vec(cells, 1).unwrap();
+        let mut opts = WrapOptionsVarWidths::default().column_options(vec![
+            None,
+            Some(
+                textwrap::Options::new(79)
+                    .subsequent_indent("> ")
+                    .word_splitter(textwrap::WordSplitter::NoHyphenation),
+            ),
+        ]);
+        let widths = [5, 9];
+        let wrapped_table = wrap_table(&table, &widths, &mut opts);
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![Cow::from("foo-"), Cow::from("bar"), Cow::from("baz")],
+                vec![Cow::from("foo-bar"), Cow::from("> baz")],
+            ]
+        );
+        let mut opts = opts.select_columns(&[1]);
+        let wrapped_table = wrap_table(&table, &[9], &mut opts);
+        assert_eq!(
+            wrapped_table.cells,
+            vec![vec![Cow::from("foo-bar"), Cow::from("> baz")]]
+        );
+    }
+
+    #[test]
+    fn test_find_broken_words() {
+        let row = ["aaaaaa".to_string(), "bbbbbb".to_string(), "c".to_string()];
+        let widths = [3, 3, 3];
+        let mut opts = WrapOptionsVarWidths::default();
+        opts.set_break_words(0);
+        opts.set_break_words(2);
+        assert_eq!(find_broken_words(&row, &widths, &mut opts, false), vec![0]);
+        let mut opts = opts.select_columns(&[1, 0]);
+        assert!(!opts.break_words_of(0));
+        assert!(opts.break_words_of(1));
+        assert_eq!(find_broken_words(&row, &widths, &mut opts, false), vec![1]);
+    }
+
+    #[test]
+    fn test_fill_table_decimal() {
+        let cells = ["Price", "1.5", "-12.25\n3", "100", "1.2345", "  7.5"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let table = Table::from_vec(cells, 6).unwrap();
+        let mut opts = WrapOptionsVarWidths::default();
+        let widths = [8];
+        let mut wrapped_table = wrap_table(&table, &widths, &mut opts);
+        fill_table(
+            &mut wrapped_table,
+            &widths,
+            &[Alignment::Decimal],
+            &[VerticalAlignment::Top],
+        );
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![Cow::from("   Price")],
+                vec![Cow::from("  1.5   ")],
+                vec![Cow::from("-12.25  "), Cow::from("  3     ")],
+                vec![Cow::from("100     ")],
+                vec![Cow::from("  1.2345")],
+                vec![Cow::from("  7.5   ")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejoin_broken_words() {
+        let cells = ["abcdefgh ij", "well-known fact", "short words"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let table = Table::from_vec(cells, 3).unwrap();
+        let cells = vec![
+            vec![Cow::from("abcd"), Cow::from("efgh"), Cow::from("ij")],
+            vec![Cow::from("well-"), Cow::from("known"), Cow::from("fact")],
+            vec![Cow::from("short"), Cow::from("  words")],
+        ];
+        let mut wrapped_table = Table::from_vec(cells, 3).unwrap();
+        rejoin_broken_words(&mut wrapped_table, &table);
+        assert_eq!(
+            wrapped_table.cells,
+            vec![
+                vec![Cow::from("abcdefgh"), Cow::from("ij")],
+                vec![Cow::from("well-known"), Cow::from("fact")],
+                vec![Cow::from("short"), Cow::from("  words")],
+            ]
+        );
+
+        // A hyphen inserted by the word splitter.
+        let cells = vec![String::from("hyphenation")];
+        let table = Table::from_vec(cells, 1).unwrap();
+        let cells = vec![vec![Cow::from("hyphen-"), Cow::from("ation")]];
+        let mut wrapped_table = Table::from_vec(cells, 1).unwrap();
+        rejoin_broken_words(&mut wrapped_table, &table);
+        assert_eq!(wrapped_table.cells, vec![vec![Cow::from("hyphenation")]]);
+
+        // The same words again further on, with a hyphen of their own.
+        let cells = vec![String::from("hyphenation hyphen-free")];
+        let table = Table::from_vec(cells, 1).unwrap();
+        let cells = vec![vec![
+            Cow::from("hyphen-"),
+            Cow::from("ation"),
+            Cow::from("hyphen-"),
+            Cow::from("free"),
+        ]];
+        let mut wrapped_table = Table::from_vec(cells, 1).unwrap();
+        rejoin_broken_words(&mut wrapped_table, &table);
+        assert_eq!(
+            wrapped_table.cells,
+            vec![vec![Cow::from("hyphenation"), Cow::from("hyphen-free")]]
+        );
+
+        // The styles reopened on every line.
+        let cells = vec![
+            String::from("\x1b[31msupercalifragilistic\x1b[0m word"),
+            String::from("\x1b[1mhyphenation\x1b[0m"),
+        ];
+        let table = Table::from_vec(cells, 2).unwrap();
+        let cells = vec![
+            vec![
+                Cow::from("\x1b[31msuperc\x1b[0m"),
+                Cow::from("\x1b[31malifra\x1b[0m"),
+                Cow::from("\x1b[31mgilist\x1b[0m"),
+                Cow::from("\x1b[31mic\x1b[0m"),
+                Cow::from("word"),
+            ],
+            vec![
+                Cow::from("\x1b[1mhyphen-\x1b[0m"),
+                Cow::from("\x1b[1m  ation\x1b[0m"),
+            ],
+        ];
+        let mut wrapped_table = Table::from_vec(cells, 2).unwrap();
+        rejoin_broken_words(&mut wrapped_table, &table);
+        let stripped: Vec<Vec<_>> = wrapped_table
+            .cells
+            .iter()
+            .map(|cell| {
+                cell.iter().map(|line| ansi::strip_sgr(line)).collect()
+            })
+            .collect();
+        assert_eq!(
+            stripped,
+            vec![
+                vec![Cow::from("supercalifragilistic"), Cow::from("word")],
+                vec![Cow::from("hyphenation")],
+            ]
+        );
+        assert_eq!(
+            wrapped_table.cells[1],
+            vec![Cow::from("\x1b[1mhyphen\x1b[0m\x1b[1mation\x1b[0m")]
+        );
+    }
+
+    #[test]
+    fn test_decimal_integer_width() {
+        assert_eq!(decimal_integer_width("-12.25"), Some(3));
+        assert_eq!(decimal_integer_width(" 100"), Some(3));
+        assert_eq!(decimal_integer_width("  -1.5 "), Some(2));
+        assert_eq!(decimal_integer_width("1.5e3"), Some(1));
+        assert_eq!(decimal_integer_width("12e-3"), Some(2));
+        assert_eq!(decimal_integer_width("\x1b[31m1.5\x1b[0m"), Some(1));
+        assert_eq!(decimal_integer_width("inf"), None);
+        assert_eq!(decimal_integer_width("NaN"), None);
+        assert_eq!(decimal_integer_width("1,5"), None);
+    }
+
+    #[test]
+    fn test_decimal_fraction_width() {
+        assert_eq!(decimal_fraction_width("-12.25"), Some(3));
+        assert_eq!(decimal_fraction_width(" 100 "), Some(0));
+        assert_eq!(decimal_fraction_width("1.5e3"), Some(4));
+        assert_eq!(decimal_fraction_width("\x1b[31m1.5\x1b[0m"), Some(2));
+        assert_eq!(decimal_fraction_width("inf"), None);
     }
 }
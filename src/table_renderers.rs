@@ -1,5 +1,10 @@
-use crate::table::{Table, TableRenderer};
+use crate::ansi;
+use crate::table::{
+    decimal_fraction_width, render_to_string, Alignment, LineStyle, Table,
+    TableRenderer, VerticalAlignment,
+};
 use std::borrow::Cow;
+use std::io::{self, Write};
 
 /// A placeholder table renderer used in tests and benchmarks.
 #[cfg(any(test, feature = "bench-brute", feature = "bench-bisect"))]
@@ -24,11 +29,13 @@ impl TableRenderer for NullTableRenderer {
 pub struct TableRenderers;
 
 impl TableRenderers {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(name: &str) -> crate::Result<Box<dyn TableRenderer>> {
         match name {
             "grid_no_header" => Ok(Box::new(GridNoHeader)),
             "grid" => Ok(Box::new(Grid)),
             "plain" => Ok(Box::new(Plain)),
+            "simple" => Ok(Box::new(Simple)),
             "github" => Ok(Box::new(Github)),
             "simple_grid" => Ok(Box::new(SimpleGrid)),
             "rounded_grid" => Ok(Box::new(RoundedGrid)),
@@ -36,30 +43,43 @@ impl TableRenderers {
             "mixed_grid" => Ok(Box::new(MixedGrid)),
             "double_grid" => Ok(Box::new(DoubleGrid)),
             "fancy_grid" => Ok(Box::new(FancyGrid)),
+            "html" => Ok(Box::new(Html::default())),
+            "html_br" => Ok(Box::new(Html {
+                line_breaks: true,
+                ..Html::default()
+            })),
+            "latex" => Ok(Box::new(Latex::default())),
+            "latex_booktabs" => Ok(Box::new(LatexBooktabs::default())),
+            "latex_longtable" => Ok(Box::new(LatexLongtable::default())),
             _ => Err(crate::Error::InvalidTableLayout(name.into())),
         }
     }
 }
 
-/// Draw a table `row` into `buf`. A line consists of
+/// Draw the `row_idx`-th row of a table into `w`, with each line of the cells
+/// styled by `style_line`. A line consists of
 ///
 /// ```plaintext
 /// <LEFT_PAD><TEXT1><COL_SEP><TEXT2><COL_SEP><TEXT3><RIGHT_PAD>\n
 /// ```
 fn draw_row(
-    buf: &mut String,
-    row: &[Vec<Cow<'_, str>>],
+    w: &mut dyn Write,
+    filled_table: &Table<Vec<Cow<'_, str>>>,
+    row_idx: usize,
+    style_line: &LineStyle<'_>,
     left_pad: &str,
     right_pad: &str,
     col_sep: &str,
-) {
+) -> io::Result<()> {
+    let row = filled_table.row(row_idx).unwrap();
     let nlines = row.first().unwrap().len();
     let ncols = row.len();
+    let mut buf = String::new();
     for i in 0..nlines {
         buf.push_str(left_pad);
         for (j, cell) in row.iter().enumerate() {
             let line = cell.get(i).unwrap();
-            buf.push_str(line);
+            buf.push_str(&style_line(row_idx, line));
             if j < ncols - 1 {
                 buf.push_str(col_sep);
             }
@@ -69,6 +89,12 @@ fn draw_row(
             buf.push('\n');
         }
     }
+    w.write_all(buf.as_bytes())
+}
+
+/// The [`LineStyle`] that leaves the lines as they are.
+fn unstyled(_row_idx: usize, line: &str) -> Cow<'_, str> {
+    Cow::from(line)
 }
 
 /// Draw a horizontal rule into `buf` given table `widths`. See tests for
@@ -93,6 +119,43 @@ fn draw_hrule(
     }
 }
 
+/// The number of lines of a table drawn with a rule above, between and below
+/// the rows, given the number of lines of each row.
+fn grid_height(row_heights: &[usize]) -> usize {
+    row_heights.iter().sum::<usize>() + row_heights.len() + 1
+}
+
+/// Draw a horizontal rule as [`draw_hrule`] does, with `title` drawn inside
+/// it after `left_pad`, e.g. `┌─ Title ───┐`. Return `None` if `title` is not
+/// a single line short enough to keep `right_pad`.
+fn draw_titled_hrule(
+    title: &str,
+    widths: &[usize],
+    rule: &str,
+    left_pad: &str,
+    right_pad: &str,
+    col_sep: &str,
+) -> Option<String> {
+    let mut hrule = String::new();
+    draw_hrule(&mut hrule, widths, rule, left_pad, right_pad, col_sep);
+    // The rules are drawn by characters one column wide each.
+    let hrule_width = hrule.chars().count();
+    let left_width = left_pad.chars().count();
+    let right_width = right_pad.chars().count();
+    let title_width = textwrap::core::display_width(title) + 2;
+    if title.contains('\n')
+        || left_width + title_width + right_width > hrule_width
+    {
+        return None;
+    }
+    let mut titled: String = hrule.chars().take(left_width).collect();
+    titled.push(' ');
+    titled.push_str(title);
+    titled.push(' ');
+    titled.extend(hrule.chars().skip(left_width + title_width));
+    Some(titled)
+}
+
 /// Sample:
 ///
 /// ```plaintext
@@ -108,11 +171,13 @@ fn draw_hrule(
 /// | nibh vitae.     |               |
 /// +-----------------+---------------+
 /// ```
+///
+/// Same as [`Grid`], except that the first row is not a header by default.
 pub struct GridNoHeader;
 
 impl TableRenderer for GridNoHeader {
     fn layout_width(&self, table_ncols: usize) -> usize {
-        3 * (table_ncols - 1) + 2 + 2
+        Grid.layout_width(table_ncols)
     }
 
     fn render_table(
@@ -120,19 +185,35 @@ impl TableRenderer for GridNoHeader {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
-        let mut hrule = String::new();
-        draw_hrule(&mut hrule, widths, "-", "+-", "-+", "-+-");
+        render_to_string(self, filled_table, widths)
+    }
 
-        let mut buf = String::new();
-        buf.push_str(&hrule);
-        let nrows = filled_table.nrows();
-        for i in 0..nrows {
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "| ", " |", " | ");
-            buf.push('\n');
-            buf.push_str(&hrule);
-        }
-        buf
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        Grid.render_to(w, filled_table, widths)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
+        Grid.render_styled_to(w, filled_table, widths, style_line)
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        Grid.rendered_height(row_heights, has_header, has_footer)
     }
 }
 
@@ -151,6 +232,8 @@ impl TableRenderer for GridNoHeader {
 /// | nibh vitae.     |               |
 /// +-----------------+---------------+
 /// ```
+///
+/// The footer, if any, is set apart by a `=` rule like the header.
 pub struct Grid;
 
 impl TableRenderer for Grid {
@@ -163,25 +246,58 @@ impl TableRenderer for Grid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "-", "+-", "-+", "-+-");
         let mut hrule2 = String::new();
         draw_hrule(&mut hrule2, widths, "=", "+=", "=+", "=+=");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "| ", " |", " | ");
-        buf.push('\n');
-        buf.push_str(&hrule2);
+        w.write_all(hrule.as_bytes())?;
         let nrows = filled_table.nrows();
-        for i in 1..nrows {
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "| ", " |", " | ");
-            buf.push('\n');
-            buf.push_str(&hrule);
+        for i in 0..nrows {
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "| ", " |", " | ")?;
+            w.write_all(b"\n")?;
+            if (i == 0 && filled_table.has_header())
+                || (i + 2 == nrows && filled_table.has_footer())
+            {
+                w.write_all(hrule2.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
         }
-        buf
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
     }
 }
 
@@ -196,6 +312,8 @@ impl TableRenderer for Grid {
 /// Sed lobortis,    Mauris enim.
 /// nibh vitae.                   
 /// ```
+///
+/// The footer, if any, is set apart by a blank line.
 pub struct Plain;
 
 impl TableRenderer for Plain {
@@ -206,18 +324,52 @@ impl TableRenderer for Plain {
     fn render_table(
         &self,
         filled_table: &Table<Vec<Cow<'_, str>>>,
-        _widths: &[usize],
+        widths: &[usize],
     ) -> String {
-        let mut buf = String::new();
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let nrows = filled_table.nrows();
         for i in 0..nrows {
-            let row = filled_table.row(i).unwrap();
-            draw_row(&mut buf, row, "", "", "  ");
+            if i > 0 && i == nrows - 1 && filled_table.has_footer() {
+                let mut blank = String::new();
+                draw_hrule(&mut blank, widths, " ", "", "", "  ");
+                w.write_all(blank.as_bytes())?;
+                w.write_all(b"\n")?;
+            }
+            draw_row(w, filled_table, i, style_line, "", "", "  ")?;
             if i < nrows - 1 {
-                buf.push('\n');
+                w.write_all(b"\n")?;
             }
         }
-        buf
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        // The blank line above the footer.
+        let nblanks = usize::from(has_footer && row_heights.len() > 1);
+        Some(row_heights.iter().sum::<usize>() + nblanks)
     }
 }
 
@@ -233,6 +385,9 @@ impl TableRenderer for Plain {
 /// Sed lobortis,    Mauris enim.
 /// nibh vitae.                   
 /// ```
+///
+/// Without a header, the rule is drawn above and below the rows instead. The
+/// footer, if any, is set apart by another rule.
 pub struct Simple;
 
 impl TableRenderer for Simple {
@@ -245,20 +400,70 @@ impl TableRenderer for Simple {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "-", "", "", "  ");
 
-        let mut buf = String::new();
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "", "", "  ");
-        buf.push('\n');
-        buf.push_str(&hrule);
         let nrows = filled_table.nrows();
-        for i in 1..nrows {
-            buf.push('\n');
-            let row = filled_table.row(i).unwrap();
-            draw_row(&mut buf, row, "", "", "  ");
+        let body_end = nrows - usize::from(filled_table.has_footer());
+        let body_start = if filled_table.has_header() {
+            draw_row(w, filled_table, 0, style_line, "", "", "  ")?;
+            w.write_all(b"\n")?;
+            w.write_all(hrule.as_bytes())?;
+            1
+        } else {
+            w.write_all(hrule.as_bytes())?;
+            0
+        };
+        for i in body_start..body_end {
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "", "", "  ")?;
+        }
+        if filled_table.has_footer() {
+            w.write_all(b"\n")?;
+            w.write_all(hrule.as_bytes())?;
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, body_end, style_line, "", "", "  ")?;
         }
-        buf
+        if !filled_table.has_header() {
+            w.write_all(b"\n")?;
+            w.write_all(hrule.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        // The rule below the header, or the rules above and below the rows,
+        // and the rule above the footer.
+        let nrules = if has_header { 1 } else { 2 } + usize::from(has_footer);
+        Some(row_heights.iter().sum::<usize>() + nrules)
     }
 }
 
@@ -274,6 +479,9 @@ impl TableRenderer for Simple {
 /// | Sed lobortis,   | Mauris enim.  |
 /// | nibh vitae.     |               |
 /// ```
+///
+/// Without a header, the rule is drawn above the rows instead. Since Markdown
+/// tables have no footer, the footer, if any, is drawn as an ordinary row.
 pub struct Github;
 
 impl TableRenderer for Github {
@@ -286,20 +494,56 @@ impl TableRenderer for Github {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "-", "|-", "-|", "-|-");
 
-        let mut buf = String::new();
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "| ", " |", " | ");
-        buf.push('\n');
-        buf.push_str(&hrule);
         let nrows = filled_table.nrows();
-        for i in 1..nrows {
-            buf.push('\n');
-            let row = filled_table.row(i).unwrap();
-            draw_row(&mut buf, row, "| ", " |", " | ");
+        let body_start = if filled_table.has_header() {
+            draw_row(w, filled_table, 0, style_line, "| ", " |", " | ")?;
+            w.write_all(b"\n")?;
+            1
+        } else {
+            0
+        };
+        w.write_all(hrule.as_bytes())?;
+        for i in body_start..nrows {
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "| ", " |", " | ")?;
         }
-        buf
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        // The rule below the header, or above the rows.
+        Some(row_heights.iter().sum::<usize>() + 1)
     }
 }
 
@@ -318,6 +562,8 @@ impl TableRenderer for Github {
 /// │ nibh vitae.     │               │
 /// └─────────────────┴───────────────┘
 /// ```
+///
+/// The footer, if any, is set apart by a double line.
 pub struct SimpleGrid;
 
 impl TableRenderer for SimpleGrid {
@@ -330,27 +576,68 @@ impl TableRenderer for SimpleGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "─", "├─", "─┤", "─┼─");
+        let mut hrule_footer = String::new();
+        draw_hrule(&mut hrule_footer, widths, "═", "╞═", "═╡", "═╪═");
         let mut hrule_first = String::new();
         draw_hrule(&mut hrule_first, widths, "─", "┌─", "─┐", "─┬─");
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "─", "└─", "─┘", "─┴─");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "│ ", " │", " │ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "│ ", " │", " │ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            buf.push_str(&hrule);
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "│ ", " │", " │ ");
-            buf.push('\n');
+            if i == nrows - 1 && filled_table.has_footer() {
+                w.write_all(hrule_footer.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "│ ", " │", " │ ")?;
+            w.write_all(b"\n")?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "─", "┌─", "─┐", "─┬─")
     }
 }
 
@@ -369,6 +656,8 @@ impl TableRenderer for SimpleGrid {
 /// │ nibh vitae.     │               │
 /// ╰─────────────────┴───────────────╯
 /// ```
+///
+/// The footer, if any, is set apart by a double line.
 pub struct RoundedGrid;
 
 impl TableRenderer for RoundedGrid {
@@ -381,27 +670,68 @@ impl TableRenderer for RoundedGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "─", "├─", "─┤", "─┼─");
+        let mut hrule_footer = String::new();
+        draw_hrule(&mut hrule_footer, widths, "═", "╞═", "═╡", "═╪═");
         let mut hrule_first = String::new();
         draw_hrule(&mut hrule_first, widths, "─", "╭─", "─╮", "─┬─");
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "─", "╰─", "─╯", "─┴─");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "│ ", " │", " │ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "│ ", " │", " │ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            buf.push_str(&hrule);
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "│ ", " │", " │ ");
-            buf.push('\n');
+            if i == nrows - 1 && filled_table.has_footer() {
+                w.write_all(hrule_footer.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "│ ", " │", " │ ")?;
+            w.write_all(b"\n")?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "─", "╭─", "─╮", "─┬─")
     }
 }
 
@@ -420,6 +750,8 @@ impl TableRenderer for RoundedGrid {
 /// ┃ nibh vitae.     ┃               ┃
 /// ┗━━━━━━━━━━━━━━━━━┻━━━━━━━━━━━━━━━┛
 /// ```
+///
+/// The footer, if any, is set apart by a light line.
 pub struct HeavyGrid;
 
 impl TableRenderer for HeavyGrid {
@@ -432,27 +764,68 @@ impl TableRenderer for HeavyGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "━", "┣━", "━┫", "━╋━");
+        let mut hrule_footer = String::new();
+        draw_hrule(&mut hrule_footer, widths, "─", "┠─", "─┨", "─╂─");
         let mut hrule_first = String::new();
         draw_hrule(&mut hrule_first, widths, "━", "┏━", "━┓", "━┳━");
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "━", "┗━", "━┛", "━┻━");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "┃ ", " ┃", " ┃ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "┃ ", " ┃", " ┃ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            buf.push_str(&hrule);
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "┃ ", " ┃", " ┃ ");
-            buf.push('\n');
+            if i == nrows - 1 && filled_table.has_footer() {
+                w.write_all(hrule_footer.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "┃ ", " ┃", " ┃ ")?;
+            w.write_all(b"\n")?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "━", "┏━", "━┓", "━┳━")
     }
 }
 
@@ -471,6 +844,8 @@ impl TableRenderer for HeavyGrid {
 /// │ nibh vitae.     │               │
 /// ┕━━━━━━━━━━━━━━━━━┷━━━━━━━━━━━━━━━┙
 /// ```
+///
+/// The footer, if any, is set apart by a double line.
 pub struct MixedGrid;
 
 impl TableRenderer for MixedGrid {
@@ -483,27 +858,68 @@ impl TableRenderer for MixedGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "━", "┝━", "━┥", "━┿━");
+        let mut hrule_footer = String::new();
+        draw_hrule(&mut hrule_footer, widths, "═", "╞═", "═╡", "═╪═");
         let mut hrule_first = String::new();
         draw_hrule(&mut hrule_first, widths, "━", "┍━", "━┑", "━┯━");
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "━", "┕━", "━┙", "━┷━");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "│ ", " │", " │ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "│ ", " │", " │ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            buf.push_str(&hrule);
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "│ ", " │", " │ ");
-            buf.push('\n');
+            if i == nrows - 1 && filled_table.has_footer() {
+                w.write_all(hrule_footer.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "│ ", " │", " │ ")?;
+            w.write_all(b"\n")?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "━", "┍━", "━┑", "━┯━")
     }
 }
 
@@ -522,6 +938,8 @@ impl TableRenderer for MixedGrid {
 /// ║ nibh vitae.     ║               ║
 /// ╚═════════════════╩═══════════════╝
 /// ```
+///
+/// The footer, if any, is set apart by a single line.
 pub struct DoubleGrid;
 
 impl TableRenderer for DoubleGrid {
@@ -534,27 +952,68 @@ impl TableRenderer for DoubleGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "═", "╠═", "═╣", "═╬═");
+        let mut hrule_footer = String::new();
+        draw_hrule(&mut hrule_footer, widths, "─", "╟─", "─╢", "─╫─");
         let mut hrule_first = String::new();
         draw_hrule(&mut hrule_first, widths, "═", "╔═", "═╗", "═╦═");
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "═", "╚═", "═╝", "═╩═");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "║ ", " ║", " ║ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "║ ", " ║", " ║ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            buf.push_str(&hrule);
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "║ ", " ║", " ║ ");
-            buf.push('\n');
+            if i == nrows - 1 && filled_table.has_footer() {
+                w.write_all(hrule_footer.as_bytes())?;
+            } else {
+                w.write_all(hrule.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "║ ", " ║", " ║ ")?;
+            w.write_all(b"\n")?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "═", "╔═", "═╗", "═╦═")
     }
 }
 
@@ -573,6 +1032,9 @@ impl TableRenderer for DoubleGrid {
 /// │ nibh vitae.     │               │
 /// ╘═════════════════╧═══════════════╛
 /// ```
+///
+/// Without a header, all rules between the rows are single lines. The footer,
+/// if any, is set apart by a double line like the header.
 pub struct FancyGrid;
 
 impl TableRenderer for FancyGrid {
@@ -585,6 +1047,25 @@ impl TableRenderer for FancyGrid {
         filled_table: &Table<Vec<Cow<'_, str>>>,
         widths: &[usize],
     ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
         let mut hrule = String::new();
         draw_hrule(&mut hrule, widths, "─", "├─", "─┤", "─┼─");
         let mut hrule1 = String::new();
@@ -594,41 +1075,941 @@ impl TableRenderer for FancyGrid {
         let mut hrule_last = String::new();
         draw_hrule(&mut hrule_last, widths, "═", "╘═", "═╛", "═╧═");
 
-        let mut buf = String::new();
-        buf.push_str(&hrule_first);
-        buf.push('\n');
-        draw_row(&mut buf, filled_table.row(0).unwrap(), "│ ", " │", " │ ");
-        buf.push('\n');
+        w.write_all(hrule_first.as_bytes())?;
+        w.write_all(b"\n")?;
+        draw_row(w, filled_table, 0, style_line, "│ ", " │", " │ ")?;
+        w.write_all(b"\n")?;
         let nrows = filled_table.nrows();
         for i in 1..nrows {
-            if i == 1 {
-                buf.push_str(&hrule1);
+            if (i == 1 && filled_table.has_header())
+                || (i == nrows - 1 && filled_table.has_footer())
+            {
+                w.write_all(hrule1.as_bytes())?;
             } else {
-                buf.push_str(&hrule);
+                w.write_all(hrule.as_bytes())?;
             }
-            buf.push('\n');
-            draw_row(&mut buf, filled_table.row(i).unwrap(), "│ ", " │", " │ ");
-            buf.push('\n');
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "│ ", " │", " │ ")?;
+            w.write_all(b"\n")?;
+        }
+        w.write_all(hrule_last.as_bytes())?;
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        _has_header: bool,
+        _has_footer: bool,
+    ) -> Option<usize> {
+        Some(grid_height(row_heights))
+    }
+
+    fn titled_top_border(
+        &self,
+        title: &str,
+        widths: &[usize],
+    ) -> Option<String> {
+        draw_titled_hrule(title, widths, "═", "╒═", "═╕", "═╤═")
+    }
+}
+
+/// Sample (of records converted by
+/// [`Table::to_records`](crate::table::Table::to_records)):
+///
+/// ```plaintext
+/// -[ RECORD 1 ]---+--------------
+/// Duis facilisis. | Maecenas
+///                 | blandit elit.
+/// Quisque ex      |
+/// nibh, auctor eu |
+/// sodales.        |
+/// -[ RECORD 2 ]---+--------------
+/// Duis facilisis. | Sed lobortis,
+///                 | nibh vitae.
+/// Quisque ex      | Mauris enim.
+/// nibh, auctor eu |
+/// sodales.        |
+/// ```
+///
+/// Every `nfields` rows of the table make up a record. The last record is
+/// labeled `-[ FOOTER ]-` instead if it's converted from a footer row. A label
+/// too long for a narrow table is cut, so that the rule ends with at least
+/// the width of a junction.
+pub struct Expanded {
+    /// The number of fields per record, i.e. the number of columns of the
+    /// table before converted into records.
+    pub nfields: usize,
+    /// Whether the last record is converted from a footer row.
+    pub has_footer: bool,
+}
+
+impl TableRenderer for Expanded {
+    fn layout_width(&self, _table_ncols: usize) -> usize {
+        3
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_styled_to(w, filled_table, widths, &unstyled)
+    }
+
+    fn render_styled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        style_line: &LineStyle<'_>,
+    ) -> io::Result<()> {
+        const JUNCTION: &str = "-+-";
+        let mut hrule = String::new();
+        draw_hrule(&mut hrule, widths, "-", "", "", JUNCTION);
+        let hrule_width = hrule.chars().count();
+
+        let nrows = filled_table.nrows();
+        for i in 0..nrows {
+            if i % self.nfields == 0 {
+                if i > 0 {
+                    w.write_all(b"\n")?;
+                }
+                // The label replaces the beginning of the rule, short of
+                // the width of a junction at its end so that the rule is no
+                // wider than the table.
+                let mut label =
+                    if self.has_footer && i + self.nfields >= nrows {
+                        String::from("-[ FOOTER ]")
+                    } else {
+                        format!("-[ RECORD {} ]", i / self.nfields + 1)
+                    };
+                label.truncate(hrule_width.saturating_sub(JUNCTION.len()));
+                let rest: String = hrule.chars().skip(label.len()).collect();
+                w.write_all(label.as_bytes())?;
+                w.write_all(rest.as_bytes())?;
+            }
+            w.write_all(b"\n")?;
+            draw_row(w, filled_table, i, style_line, "", "", " | ")?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the lines of a `cell` with the colors removed, and with the padding
+/// added by [`fill_table`](crate::table::fill_table) to a left-aligned column,
+/// i.e. the spaces after the text and the blank lines around it. The leading
+/// whitespace, e.g. an indent, is part of the text and kept. The cells of
+/// markup layouts aren't filled by `tabulate` in the first place.
+fn unpadded_lines<'a>(cell: &'a [Cow<'_, str>]) -> Vec<Cow<'a, str>> {
+    let lines: Vec<Cow<str>> = cell
+        .iter()
+        .map(|s| match ansi::strip_sgr(s) {
+            Cow::Borrowed(s) => Cow::from(s.trim_end()),
+            Cow::Owned(s) => Cow::from(s.trim_end().to_owned()),
+        })
+        .collect();
+    // The blank lines added above and below the text by the vertical
+    // alignment.
+    let Some(first) = lines.iter().position(|s| !s.is_empty()) else {
+        return vec![];
+    };
+    let last = lines.iter().rposition(|s| !s.is_empty()).unwrap();
+    lines[first..=last].to_vec()
+}
+
+/// Return the number of digits to pad `line` by on the right, so that its
+/// decimal point lines up with that of the number with the widest fractional
+/// part, whose width is `max_fraction_width`. Lines that are not numbers
+/// aren't padded.
+fn decimal_padding(line: &str, max_fraction_width: usize) -> usize {
+    decimal_fraction_width(line)
+        .map_or(0, |w| max_fraction_width.saturating_sub(w))
+}
+
+/// Escape `<`, `>` and `&` in `text` so that it can be put into HTML.
+fn escape_html(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '>', '&']) {
+        return Cow::from(text);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::from(escaped)
+}
+
+/// Alignments of the columns of a markup layout, which aligns the text by
+/// itself instead of padding the cells. The columns beyond the alignments are
+/// aligned left and top. The decimal points of the numbers in a column aligned
+/// by [`Alignment::Decimal`] are lined up by right-aligning the numbers,
+/// padded on the right by blanks as wide as digits.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnAlignments {
+    /// Horizontal alignment of each column.
+    pub horizontal: Vec<Alignment>,
+    /// Vertical alignment of each column.
+    pub vertical: Vec<VerticalAlignment>,
+}
+
+impl ColumnAlignments {
+    /// Return the horizontal alignment of the `col_idx`-th column.
+    fn horizontal(&self, col_idx: usize) -> Alignment {
+        self.horizontal.get(col_idx).copied().unwrap_or_default()
+    }
+
+    /// Return the vertical alignment of the `col_idx`-th column.
+    fn vertical(&self, col_idx: usize) -> VerticalAlignment {
+        self.vertical.get(col_idx).copied().unwrap_or_default()
+    }
+
+    /// Return the max display width of the fractional parts of the numbers,
+    /// as given by [`decimal_fraction_width`], in each column of
+    /// `filled_table` aligned by [`Alignment::Decimal`], or 0 for the other
+    /// columns.
+    fn max_fraction_widths(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+    ) -> Vec<usize> {
+        (0..filled_table.ncols())
+            .map(|j| match self.horizontal(j) {
+                Alignment::Decimal => (0..filled_table.nrows())
+                    .flat_map(|i| filled_table.get(i, j).unwrap().iter())
+                    .filter_map(|line| decimal_fraction_width(line))
+                    .max()
+                    .unwrap_or(0),
+                _ => 0,
+            })
+            .collect()
+    }
+
+    /// Return the `style` attribute of the cells of the `col_idx`-th column,
+    /// e.g. ` style="text-align: right"`, or an empty string if the column is
+    /// aligned left and top. Decimal columns are aligned right, since HTML
+    /// has no way to line up the decimal points.
+    fn html_style(&self, col_idx: usize) -> String {
+        let mut props = Vec::new();
+        match self.horizontal(col_idx) {
+            Alignment::Left => {}
+            Alignment::Right | Alignment::Decimal => {
+                props.push("text-align: right")
+            }
+            Alignment::Center => props.push("text-align: center"),
+        }
+        match self.vertical(col_idx) {
+            VerticalAlignment::Top => {}
+            VerticalAlignment::Middle => props.push("vertical-align: middle"),
+            VerticalAlignment::Bottom => props.push("vertical-align: bottom"),
+        }
+        if props.is_empty() {
+            String::new()
+        } else {
+            format!(" style=\"{}\"", props.join("; "))
+        }
+    }
+}
+
+/// Sample (with `line_breaks` set to `true`):
+///
+/// ```plaintext
+/// <table>
+/// <thead>
+/// <tr><th>Duis facilisis.</th><th>Quisque ex<br>nibh, auctor<br>eu sodales.</th></tr>
+/// </thead>
+/// <tbody>
+/// <tr><td>Maecenas<br>blandit elit.</td><td></td></tr>
+/// <tr><td>Sed lobortis,<br>nibh vitae.</td><td>Mauris enim.</td></tr>
+/// </tbody>
+/// </table>
+/// ```
+///
+/// If `line_breaks` is `false`, the wrapped lines of a cell are joined by
+/// spaces instead, so that the browser may rewrap them. Since the table is
+/// not drawn with characters, the layout takes no width. Without a header,
+/// the `<thead>` is left out. The footer, if any, goes into the `<tfoot>`.
+/// The title and the caption, if any, go into the `<caption>`, separated by a
+/// `<br>` break. The cells of the columns not aligned left or top are given a
+/// `style` with the `text-align` and the `vertical-align` of the column.
+#[derive(Default)]
+pub struct Html {
+    /// Whether to keep the wrapped lines as `<br>` breaks.
+    pub line_breaks: bool,
+    /// Alignments of the columns.
+    pub alignments: ColumnAlignments,
+}
+
+impl Html {
+    /// Draw a table `row` into `w`, with the cells enclosed by `tag`. The
+    /// numbers are padded by figure spaces as told by `max_fraction_widths`,
+    /// see [`ColumnAlignments::max_fraction_widths`].
+    fn draw_row(
+        &self,
+        w: &mut dyn Write,
+        row: &[Vec<Cow<'_, str>>],
+        tag: &str,
+        max_fraction_widths: &[usize],
+    ) -> io::Result<()> {
+        let line_sep = if self.line_breaks { "<br>" } else { " " };
+        let mut buf = String::new();
+        buf.push_str("<tr>");
+        for (j, cell) in row.iter().enumerate() {
+            let style = self.alignments.html_style(j);
+            buf.push_str(&format!("<{}{}>", tag, style));
+            for (i, line) in unpadded_lines(cell).iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(line_sep);
+                }
+                buf.push_str(&escape_html(line));
+                let padding = decimal_padding(line, max_fraction_widths[j]);
+                buf.push_str(&"&#8199;".repeat(padding));
+            }
+            buf.push_str(&format!("</{}>", tag));
+        }
+        buf.push_str("</tr>");
+        w.write_all(buf.as_bytes())
+    }
+}
+
+impl TableRenderer for Html {
+    fn layout_width(&self, _table_ncols: usize) -> usize {
+        0
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_titled_to(w, filled_table, widths, None, None)
+    }
+
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        _widths: &[usize],
+        title: Option<&str>,
+        caption: Option<&str>,
+    ) -> io::Result<()> {
+        let max_fraction_widths =
+            self.alignments.max_fraction_widths(filled_table);
+        w.write_all("<table>\n".as_bytes())?;
+        if title.is_some() || caption.is_some() {
+            let lines: Vec<_> =
+                title.into_iter().chain(caption).map(escape_html).collect();
+            w.write_all(
+                format!("<caption>{}</caption>\n", lines.join("<br>"))
+                    .as_bytes(),
+            )?;
+        }
+        let body_start = if filled_table.has_header() {
+            w.write_all("<thead>\n".as_bytes())?;
+            self.draw_row(
+                w,
+                filled_table.row(0).unwrap(),
+                "th",
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n</thead>\n".as_bytes())?;
+            1
+        } else {
+            0
+        };
+        w.write_all("<tbody>\n".as_bytes())?;
+        let nrows = filled_table.nrows();
+        let body_end = nrows - usize::from(filled_table.has_footer());
+        for i in body_start..body_end {
+            self.draw_row(
+                w,
+                filled_table.row(i).unwrap(),
+                "td",
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        w.write_all("</tbody>\n".as_bytes())?;
+        if filled_table.has_footer() {
+            w.write_all("<tfoot>\n".as_bytes())?;
+            self.draw_row(
+                w,
+                filled_table.row(body_end).unwrap(),
+                "td",
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n</tfoot>\n".as_bytes())?;
+        }
+        w.write_all("</table>".as_bytes())?;
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        false
+    }
+
+    fn is_markup(&self) -> bool {
+        true
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.alignments = ColumnAlignments {
+            horizontal: alignments.to_vec(),
+            vertical: vertical_alignments.to_vec(),
+        };
+    }
+
+    fn rewraps_text(&self) -> bool {
+        !self.line_breaks
+    }
+}
+
+/// Width of a character in `em`, used to convert column widths to LaTeX
+/// lengths. It's about the average width of a character in Computer Modern.
+const LATEX_EM_PER_CHAR: f64 = 0.5;
+
+/// Escape LaTeX special characters in `text`.
+fn escape_latex(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '%', '$', '#', '_', '{', '}', '~', '^', '\\']) {
+        return Cow::from(text);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::from(escaped)
+}
+
+/// Draw the column specification, e.g. `{p{7.5em}p{6.5em}}`, into `w`
+/// given table `widths` and the `alignments` of the columns. A column aligned
+/// middle or bottom is an `m` or a `b` column instead, and a column aligned
+/// right or center is prefixed by `\raggedleft` or `\centering`, which
+/// requires `\usepackage{array}`.
+fn draw_latex_col_spec(
+    w: &mut dyn Write,
+    widths: &[usize],
+    alignments: &ColumnAlignments,
+) -> io::Result<()> {
+    let mut buf = String::new();
+    buf.push('{');
+    for (j, w) in widths.iter().enumerate() {
+        match alignments.horizontal(j) {
+            Alignment::Left => {}
+            Alignment::Right | Alignment::Decimal => {
+                buf.push_str(">{\\raggedleft\\arraybackslash}")
+            }
+            Alignment::Center => {
+                buf.push_str(">{\\centering\\arraybackslash}")
+            }
+        }
+        let col_type = match alignments.vertical(j) {
+            VerticalAlignment::Top => 'p',
+            VerticalAlignment::Middle => 'm',
+            VerticalAlignment::Bottom => 'b',
+        };
+        buf.push_str(&format!(
+            "{}{{{:.1}em}}",
+            col_type,
+            *w as f64 * LATEX_EM_PER_CHAR
+        ));
+    }
+    buf.push('}');
+    w.write_all(buf.as_bytes())
+}
+
+/// Draw the `\caption` made of `title` and `caption` into `w`, joining them
+/// by a space like the lines of a cell.
+fn draw_latex_caption(
+    w: &mut dyn Write,
+    title: Option<&str>,
+    caption: Option<&str>,
+) -> io::Result<()> {
+    let lines: Vec<_> =
+        title.into_iter().chain(caption).map(escape_latex).collect();
+    w.write_all(format!("\\caption{{{}}}", lines.join(" ")).as_bytes())
+}
+
+/// Render a filled table by `renderer` into `w`, in a `table` float with the
+/// `\caption` made of `title` and `caption` if there's any.
+fn render_latex_float(
+    renderer: &dyn TableRenderer,
+    w: &mut dyn Write,
+    filled_table: &Table<Vec<Cow<'_, str>>>,
+    widths: &[usize],
+    title: Option<&str>,
+    caption: Option<&str>,
+) -> io::Result<()> {
+    if title.is_none() && caption.is_none() {
+        return renderer.render_to(w, filled_table, widths);
+    }
+    w.write_all("\\begin{table}\n".as_bytes())?;
+    draw_latex_caption(w, title, caption)?;
+    w.write_all(b"\n")?;
+    renderer.render_to(w, filled_table, widths)?;
+    w.write_all("\n\\end{table}".as_bytes())
+}
+
+/// Draw a table `row` into `w`, joining the wrapped lines of each cell by
+/// spaces since LaTeX rewraps the text in `p` columns anyway. The numbers are
+/// padded by `\phantom` digits as told by `max_fraction_widths`, see
+/// [`ColumnAlignments::max_fraction_widths`].
+fn draw_latex_row(
+    w: &mut dyn Write,
+    row: &[Vec<Cow<'_, str>>],
+    max_fraction_widths: &[usize],
+) -> io::Result<()> {
+    let mut buf = String::new();
+    for (j, cell) in row.iter().enumerate() {
+        if j > 0 {
+            buf.push_str(" & ");
+        }
+        for (i, line) in unpadded_lines(cell).iter().enumerate() {
+            if i > 0 {
+                buf.push(' ');
+            }
+            buf.push_str(&escape_latex(line));
+            let padding = decimal_padding(line, max_fraction_widths[j]);
+            if padding > 0 {
+                buf.push_str(&format!("\\phantom{{{}}}", "0".repeat(padding)));
+            }
+        }
+    }
+    buf.push_str(" \\\\");
+    w.write_all(buf.as_bytes())
+}
+
+/// Sample:
+///
+/// ```plaintext
+/// \begin{tabular}{p{7.5em}p{6.5em}}
+/// \hline
+/// Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+/// \hline
+/// Maecenas blandit elit. &  \\
+/// Sed lobortis, nibh vitae. & Mauris enim. \\
+/// \hline
+/// \end{tabular}
+/// ```
+///
+/// The width of each `p` column is derived from the column width. Like
+/// [`Html`], the layout takes no width. Without a header, the rows are
+/// enclosed by a pair of rules only. The footer, if any, is set apart by a
+/// rule like the header. With a title or a caption, the table is put in a
+/// `table` float with the `\caption` above it.
+#[derive(Default)]
+pub struct Latex {
+    /// Alignments of the columns.
+    pub alignments: ColumnAlignments,
+}
+
+impl TableRenderer for Latex {
+    fn layout_width(&self, _table_ncols: usize) -> usize {
+        0
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        w.write_all("\\begin{tabular}".as_bytes())?;
+        let max_fraction_widths =
+            self.alignments.max_fraction_widths(filled_table);
+        draw_latex_col_spec(w, widths, &self.alignments)?;
+        w.write_all("\n\\hline\n".as_bytes())?;
+        let body_start = if filled_table.has_header() {
+            draw_latex_row(
+                w,
+                filled_table.row(0).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n\\hline\n".as_bytes())?;
+            1
+        } else {
+            0
+        };
+        let nrows = filled_table.nrows();
+        let body_end = nrows - usize::from(filled_table.has_footer());
+        for i in body_start..body_end {
+            draw_latex_row(
+                w,
+                filled_table.row(i).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        if filled_table.has_footer() {
+            w.write_all("\\hline\n".as_bytes())?;
+            draw_latex_row(
+                w,
+                filled_table.row(body_end).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        w.write_all("\\hline\n\\end{tabular}".as_bytes())?;
+        Ok(())
+    }
+
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        title: Option<&str>,
+        caption: Option<&str>,
+    ) -> io::Result<()> {
+        render_latex_float(self, w, filled_table, widths, title, caption)
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        false
+    }
+
+    fn is_markup(&self) -> bool {
+        true
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.alignments = ColumnAlignments {
+            horizontal: alignments.to_vec(),
+            vertical: vertical_alignments.to_vec(),
+        };
+    }
+
+    fn rewraps_text(&self) -> bool {
+        true
+    }
+}
+
+/// Sample:
+///
+/// ```plaintext
+/// \begin{tabular}{p{7.5em}p{6.5em}}
+/// \toprule
+/// Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+/// \midrule
+/// Maecenas blandit elit. &  \\
+/// Sed lobortis, nibh vitae. & Mauris enim. \\
+/// \bottomrule
+/// \end{tabular}
+/// ```
+///
+/// The footer, if any, is set apart by a `\midrule` like the header. The
+/// rules require `\usepackage{booktabs}`. A title or a caption is made into
+/// the `\caption` as in [`Latex`].
+#[derive(Default)]
+pub struct LatexBooktabs {
+    /// Alignments of the columns.
+    pub alignments: ColumnAlignments,
+}
+
+impl TableRenderer for LatexBooktabs {
+    fn layout_width(&self, _table_ncols: usize) -> usize {
+        0
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        w.write_all("\\begin{tabular}".as_bytes())?;
+        let max_fraction_widths =
+            self.alignments.max_fraction_widths(filled_table);
+        draw_latex_col_spec(w, widths, &self.alignments)?;
+        w.write_all("\n\\toprule\n".as_bytes())?;
+        let body_start = if filled_table.has_header() {
+            draw_latex_row(
+                w,
+                filled_table.row(0).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n\\midrule\n".as_bytes())?;
+            1
+        } else {
+            0
+        };
+        let nrows = filled_table.nrows();
+        let body_end = nrows - usize::from(filled_table.has_footer());
+        for i in body_start..body_end {
+            draw_latex_row(
+                w,
+                filled_table.row(i).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        if filled_table.has_footer() {
+            w.write_all("\\midrule\n".as_bytes())?;
+            draw_latex_row(
+                w,
+                filled_table.row(body_end).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        w.write_all("\\bottomrule\n\\end{tabular}".as_bytes())?;
+        Ok(())
+    }
+
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        title: Option<&str>,
+        caption: Option<&str>,
+    ) -> io::Result<()> {
+        render_latex_float(self, w, filled_table, widths, title, caption)
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        false
+    }
+
+    fn is_markup(&self) -> bool {
+        true
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.alignments = ColumnAlignments {
+            horizontal: alignments.to_vec(),
+            vertical: vertical_alignments.to_vec(),
+        };
+    }
+
+    fn rewraps_text(&self) -> bool {
+        true
+    }
+}
+
+/// Sample:
+///
+/// ```plaintext
+/// \begin{longtable}{p{7.5em}p{6.5em}}
+/// \hline
+/// Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+/// \hline
+/// \endhead
+/// Maecenas blandit elit. &  \\
+/// Sed lobortis, nibh vitae. & Mauris enim. \\
+/// \hline
+/// \end{longtable}
+/// ```
+///
+/// The header row, if any, is repeated on every page. The footer row, if any,
+/// ends the last page only, as the `\endlastfoot` of the table. The title and
+/// the caption, if any, are made into the `\caption` at the top. The
+/// environment requires `\usepackage{longtable}`.
+#[derive(Default)]
+pub struct LatexLongtable {
+    /// Alignments of the columns.
+    pub alignments: ColumnAlignments,
+}
+
+impl TableRenderer for LatexLongtable {
+    fn layout_width(&self, _table_ncols: usize) -> usize {
+        0
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        self.render_titled_to(w, filled_table, widths, None, None)
+    }
+
+    fn render_titled_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+        title: Option<&str>,
+        caption: Option<&str>,
+    ) -> io::Result<()> {
+        w.write_all("\\begin{longtable}".as_bytes())?;
+        let max_fraction_widths =
+            self.alignments.max_fraction_widths(filled_table);
+        draw_latex_col_spec(w, widths, &self.alignments)?;
+        if title.is_some() || caption.is_some() {
+            w.write_all(b"\n")?;
+            draw_latex_caption(w, title, caption)?;
+            w.write_all(" \\\\".as_bytes())?;
         }
-        buf.push_str(&hrule_last);
-        buf
+        w.write_all("\n\\hline\n".as_bytes())?;
+        let body_start = if filled_table.has_header() {
+            draw_latex_row(
+                w,
+                filled_table.row(0).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n\\hline\n\\endhead\n".as_bytes())?;
+            1
+        } else {
+            if filled_table.has_footer() {
+                // Otherwise the rule would become part of the footer.
+                w.write_all("\\endhead\n".as_bytes())?;
+            }
+            0
+        };
+        let nrows = filled_table.nrows();
+        let body_end = nrows - usize::from(filled_table.has_footer());
+        if filled_table.has_footer() {
+            w.write_all("\\hline\n".as_bytes())?;
+            draw_latex_row(
+                w,
+                filled_table.row(body_end).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all("\n\\hline\n\\endlastfoot\n".as_bytes())?;
+        }
+        for i in body_start..body_end {
+            draw_latex_row(
+                w,
+                filled_table.row(i).unwrap(),
+                &max_fraction_widths,
+            )?;
+            w.write_all(b"\n")?;
+        }
+        if !filled_table.has_footer() {
+            w.write_all("\\hline\n".as_bytes())?;
+        }
+        w.write_all("\\end{longtable}".as_bytes())?;
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        true
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        false
+    }
+
+    fn is_markup(&self) -> bool {
+        true
+    }
+
+    fn set_alignments(
+        &mut self,
+        alignments: &[Alignment],
+        vertical_alignments: &[VerticalAlignment],
+    ) {
+        self.alignments = ColumnAlignments {
+            horizontal: alignments.to_vec(),
+            vertical: vertical_alignments.to_vec(),
+        };
+    }
+
+    fn rewraps_text(&self) -> bool {
+        true
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        draw_hrule, DoubleGrid, FancyGrid, Github, Grid, GridNoHeader,
-        HeavyGrid, MixedGrid, Plain, RoundedGrid, Simple, SimpleGrid,
+        draw_hrule, escape_html, escape_latex, unpadded_lines, DoubleGrid,
+        Expanded, FancyGrid, Github, Grid, GridNoHeader, HeavyGrid, Html,
+        Latex, LatexBooktabs, LatexLongtable, MixedGrid, Plain, RoundedGrid,
+        Simple, SimpleGrid,
     };
-    use crate::column_planner::complete_user_widths;
+    use crate::column_planner::{complete_user_widths, PlanOptions};
     use crate::io::ReadOptions;
     use crate::table::{
-        fill_table, wrap_table, Table, TableRenderer, WrapOptionsVarWidths,
+        fill_table, wrap_table, Alignment, Table, TableRenderer,
+        VerticalAlignment, WrapOptionsVarWidths,
     };
     use std::borrow::Cow;
     use std::fs::File;
-    use std::io::BufReader;
+    use std::io::{self, BufReader, Write};
 
     #[test]
     fn test_draw_hrule() {
@@ -638,19 +2019,33 @@ mod tests {
     }
 
     fn read_lipsum_text() -> crate::Result<Table<String>> {
-        let file = File::open("../example-texts/lipsum.txt")?;
+        let file = File::open(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/example-texts/lipsum.txt"
+        ))?;
         let file = BufReader::new(file);
         let read_opts = ReadOptions::default();
         let table = Table::from_bufread(file, &read_opts)?;
         Ok(table)
     }
 
+    type FilledTable<'a> = Table<Vec<Cow<'a, str>>>;
+
     fn fill_lipsum_table<'a>(
         table: &'a mut Table<String>,
         renderer: &dyn TableRenderer,
-    ) -> crate::Result<(Vec<usize>, Table<Vec<Cow<'a, str>>>)> {
+    ) -> crate::Result<(Vec<usize>, FilledTable<'a>)> {
+        fill_lipsum_table_valign(table, renderer, VerticalAlignment::Top)
+    }
+
+    fn fill_lipsum_table_valign<'a>(
+        table: &'a mut Table<String>,
+        renderer: &dyn TableRenderer,
+        vertical_alignment: VerticalAlignment,
+    ) -> crate::Result<(Vec<usize>, FilledTable<'a>)> {
         let ncols = table.ncols();
         let mut wrap_opts = WrapOptionsVarWidths::default();
+        table.set_header(renderer.has_header_by_default());
         table.transpose();
         let widths = complete_user_widths(
             vec![None; ncols],
@@ -658,13 +2053,61 @@ mod tests {
             table,
             renderer,
             &mut wrap_opts,
+            &PlanOptions::default(),
         )?;
         table.transpose();
         let mut wrapped_table = wrap_table(table, &widths, &mut wrap_opts);
-        fill_table(&mut wrapped_table, &widths);
+        fill_table(
+            &mut wrapped_table,
+            &widths,
+            &vec![Alignment::Left; ncols],
+            &vec![vertical_alignment; ncols],
+        );
         Ok((widths, wrapped_table))
     }
 
+    /// A writer that takes up to `capacity` bytes, and then fails as if the
+    /// reader had gone away.
+    struct BrokenPipeWriter {
+        buf: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl Write for BrokenPipeWriter {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            let n = data.len().min(self.capacity - self.buf.len());
+            if n == 0 && !data.is_empty() {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            self.buf.extend_from_slice(&data[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_render_to_broken_pipe() -> crate::Result<()> {
+        let renderer = Grid;
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let rendered = renderer.render_table(&wrapped_table, &widths);
+        // The rows are streamed into the writer until it fails.
+        let capacity = rendered.len() / 2;
+        let mut w = BrokenPipeWriter {
+            buf: vec![],
+            capacity,
+        };
+        let err = renderer
+            .render_to(&mut w, &wrapped_table, &widths)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(w.buf, rendered.as_bytes()[..capacity]);
+        Ok(())
+    }
+
     #[test]
     fn test_grid_no_header() -> crate::Result<()> {
         let renderer = GridNoHeader;
@@ -688,6 +2131,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_grid_no_header_with_header() -> crate::Result<()> {
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &GridNoHeader)?;
+        wrapped_table.set_header(true);
+        assert_eq!(
+            GridNoHeader.render_table(&wrapped_table, &widths),
+            Grid.render_table(&wrapped_table, &widths)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_grid() -> crate::Result<()> {
         let renderer = Grid;
@@ -730,6 +2186,30 @@ nibh vitae.                   "#
         Ok(())
     }
 
+    #[test]
+    fn test_plain_with_footer() -> crate::Result<()> {
+        let renderer = Plain;
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_footer(true);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"Duis facilisis.  Quisque ex   
+                 nibh, auctor 
+                 eu sodales.  
+Maecenas                      
+blandit elit.                 
+                              
+Sed lobortis,    Mauris enim. 
+nibh vitae.                   "#
+        );
+        assert_eq!(renderer.rendered_height(&[3, 2, 2], true, true), Some(8));
+        assert_eq!(renderer.rendered_height(&[2], false, true), Some(2));
+        Ok(())
+    }
+
     #[test]
     fn test_simple() -> crate::Result<()> {
         let renderer = Simple;
@@ -750,6 +2230,30 @@ nibh vitae.                   "#
         Ok(())
     }
 
+    #[test]
+    fn test_simple_without_header() -> crate::Result<()> {
+        let renderer = Simple;
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_header(false);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"---------------  -------------
+Duis facilisis.  Quisque ex   
+                 nibh, auctor 
+                 eu sodales.  
+Maecenas                      
+blandit elit.                 
+Sed lobortis,    Mauris enim. 
+nibh vitae.                   
+---------------  -------------"#
+        );
+        assert_eq!(renderer.rendered_height(&[3, 2, 2], false, false), Some(9));
+        Ok(())
+    }
+
     #[test]
     fn test_github() -> crate::Result<()> {
         let renderer = Github;
@@ -770,6 +2274,28 @@ nibh vitae.                   "#
         Ok(())
     }
 
+    #[test]
+    fn test_github_without_header() -> crate::Result<()> {
+        let renderer = Github;
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_header(false);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"|-----------------|---------------|
+| Duis facilisis. | Quisque ex    |
+|                 | nibh, auctor  |
+|                 | eu sodales.   |
+| Maecenas        |               |
+| blandit elit.   |               |
+| Sed lobortis,   | Mauris enim.  |
+| nibh vitae.     |               |"#
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_simple_grid() -> crate::Result<()> {
         let renderer = SimpleGrid;
@@ -793,6 +2319,33 @@ nibh vitae.                   "#
         Ok(())
     }
 
+    #[test]
+    fn test_box_grids_with_footer() -> crate::Result<()> {
+        let renderers: [(&dyn TableRenderer, &str); 5] = [
+            (&SimpleGrid, "╞═════════════════╪═══════════════╡"),
+            (&RoundedGrid, "╞═════════════════╪═══════════════╡"),
+            (&HeavyGrid, "┠─────────────────╂───────────────┨"),
+            (&MixedGrid, "╞═════════════════╪═══════════════╡"),
+            (&DoubleGrid, "╟─────────────────╫───────────────╢"),
+        ];
+        for (renderer, footer_rule) in renderers {
+            let mut table = read_lipsum_text()?;
+            let (widths, mut wrapped_table) =
+                fill_lipsum_table(&mut table, renderer)?;
+            let s = renderer.render_table(&wrapped_table, &widths);
+            assert_ne!(s.lines().nth(7), Some(footer_rule));
+            wrapped_table.set_footer(true);
+            let s = renderer.render_table(&wrapped_table, &widths);
+            assert_eq!(s.lines().nth(7), Some(footer_rule));
+            assert_eq!(s.lines().count(), 11);
+            assert_eq!(
+                renderer.rendered_height(&[3, 2, 2], true, true),
+                Some(11)
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_rounded_grid() -> crate::Result<()> {
         let renderer = RoundedGrid;
@@ -907,4 +2460,401 @@ nibh vitae.                   "#
         );
         Ok(())
     }
+
+    #[test]
+    fn test_fancy_grid_with_footer() -> crate::Result<()> {
+        let renderer = FancyGrid;
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_footer(true);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"╒═════════════════╤═══════════════╕
+│ Duis facilisis. │ Quisque ex    │
+│                 │ nibh, auctor  │
+│                 │ eu sodales.   │
+╞═════════════════╪═══════════════╡
+│ Maecenas        │               │
+│ blandit elit.   │               │
+╞═════════════════╪═══════════════╡
+│ Sed lobortis,   │ Mauris enim.  │
+│ nibh vitae.     │               │
+╘═════════════════╧═══════════════╛"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_with_footer() -> crate::Result<()> {
+        let renderer = Simple;
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_footer(true);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"Duis facilisis.  Quisque ex   
+                 nibh, auctor 
+                 eu sodales.  
+---------------  -------------
+Maecenas                      
+blandit elit.                 
+---------------  -------------
+Sed lobortis,    Mauris enim. 
+nibh vitae.                   "#
+        );
+        assert_eq!(renderer.rendered_height(&[3, 2, 2], true, true), Some(9));
+        Ok(())
+    }
+
+    #[test]
+    fn test_expanded() -> crate::Result<()> {
+        let mut renderer = Expanded {
+            nfields: 2,
+            has_footer: false,
+        };
+        let mut table = read_lipsum_text()?.to_records()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"-[ RECORD 1 ]---+--------------
+Duis facilisis. | Maecenas     
+                | blandit elit.
+Quisque ex      |              
+nibh, auctor eu |              
+sodales.        |              
+-[ RECORD 2 ]---+--------------
+Duis facilisis. | Sed lobortis,
+                | nibh vitae.  
+Quisque ex      | Mauris enim. 
+nibh, auctor eu |              
+sodales.        |              "#
+        );
+
+        renderer.has_footer = true;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s.lines()
+                .filter(|line| line.starts_with("-["))
+                .collect::<Vec<_>>(),
+            vec![
+                "-[ RECORD 1 ]---+--------------",
+                "-[ FOOTER ]-----+--------------"
+            ]
+        );
+
+        // The labels are cut to the width of the table.
+        let s = renderer.render_table(&wrapped_table, &[2, 4]);
+        assert_eq!(
+            s.lines()
+                .filter(|line| line.starts_with("-["))
+                .collect::<Vec<_>>(),
+            vec!["-[ REC---", "-[ FOO---"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpadded_lines() {
+        let cell =
+            ["      ", "def f():  ", "    pass  ", "      "].map(Cow::from);
+        assert_eq!(unpadded_lines(&cell), vec!["def f():", "    pass"]);
+        assert!(unpadded_lines(&[Cow::from("   ")]).is_empty());
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("a < b && c > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+        assert!(matches!(escape_html("abc"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_html() -> crate::Result<()> {
+        let renderer = Html {
+            line_breaks: true,
+            ..Html::default()
+        };
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"<table>
+<thead>
+<tr><th>Duis facilisis.</th><th>Quisque ex<br>nibh, auctor<br>eu sodales.</th></tr>
+</thead>
+<tbody>
+<tr><td>Maecenas<br>blandit elit.</td><td></td></tr>
+<tr><td>Sed lobortis,<br>nibh vitae.</td><td>Mauris enim.</td></tr>
+</tbody>
+</table>"#
+        );
+
+        let renderer = Html::default();
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"<table>
+<thead>
+<tr><th>Duis facilisis.</th><th>Quisque ex nibh, auctor eu sodales.</th></tr>
+</thead>
+<tbody>
+<tr><td>Maecenas blandit elit.</td><td></td></tr>
+<tr><td>Sed lobortis, nibh vitae.</td><td>Mauris enim.</td></tr>
+</tbody>
+</table>"#
+        );
+        Ok(())
+    }
+
+    /// The padding added by `fill_table`, e.g. for the bottom alignment, is
+    /// left out, since the text is aligned by the styles of the cells.
+    #[test]
+    fn test_html_filled_cells() -> crate::Result<()> {
+        let mut renderer = Html {
+            line_breaks: true,
+            ..Html::default()
+        };
+        renderer.set_alignments(
+            &[Alignment::Right, Alignment::Left],
+            &[VerticalAlignment::Bottom; 2],
+        );
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table_valign(
+            &mut table,
+            &renderer,
+            VerticalAlignment::Bottom,
+        )?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"<table>
+<thead>
+<tr><th style="text-align: right; vertical-align: bottom">Duis facilisis.</th><th style="vertical-align: bottom">Quisque ex<br>nibh, auctor<br>eu sodales.</th></tr>
+</thead>
+<tbody>
+<tr><td style="text-align: right; vertical-align: bottom">Maecenas<br>blandit elit.</td><td style="vertical-align: bottom"></td></tr>
+<tr><td style="text-align: right; vertical-align: bottom">Sed lobortis,<br>nibh vitae.</td><td style="vertical-align: bottom">Mauris enim.</td></tr>
+</tbody>
+</table>"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_decimal() {
+        let cells = ["1.5", "-12.25", "100", "n/a"]
+            .into_iter()
+            .map(|s| vec![Cow::from(s)])
+            .collect();
+        let table = Table::from_vec(cells, 4).unwrap();
+        let mut renderer = Html::default();
+        renderer.set_alignments(&[Alignment::Decimal], &[]);
+        assert_eq!(
+            renderer.render_table(&table, &[6]),
+            r#"<table>
+<tbody>
+<tr><td style="text-align: right">1.5&#8199;</td></tr>
+<tr><td style="text-align: right">-12.25</td></tr>
+<tr><td style="text-align: right">100&#8199;&#8199;&#8199;</td></tr>
+<tr><td style="text-align: right">n/a</td></tr>
+</tbody>
+</table>"#
+        );
+    }
+
+    #[test]
+    fn test_html_without_header() -> crate::Result<()> {
+        let renderer = Html::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_header(false);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"<table>
+<tbody>
+<tr><td>Duis facilisis.</td><td>Quisque ex nibh, auctor eu sodales.</td></tr>
+<tr><td>Maecenas blandit elit.</td><td></td></tr>
+<tr><td>Sed lobortis, nibh vitae.</td><td>Mauris enim.</td></tr>
+</tbody>
+</table>"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape_latex() {
+        assert_eq!(
+            escape_latex(r"50% of $x_1 & {y} #2 ~ ^ \"),
+            r"50\% of \$x\_1 \& \{y\} \#2 \textasciitilde{} \textasciicircum{} \textbackslash{}"
+        );
+        assert!(matches!(escape_latex("abc"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_latex() -> crate::Result<()> {
+        let renderer = Latex::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{tabular}{p{7.5em}p{6.5em}}
+\hline
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+\hline
+Maecenas blandit elit. &  \\
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\hline
+\end{tabular}"#
+        );
+        Ok(())
+    }
+
+    /// The padding added by `fill_table`, e.g. for the bottom alignment, is
+    /// left out, since the text is aligned by the types of the columns.
+    #[test]
+    fn test_latex_filled_cells() -> crate::Result<()> {
+        let mut renderer = Latex::default();
+        renderer.set_alignments(
+            &[Alignment::Right, Alignment::Left],
+            &[VerticalAlignment::Bottom; 2],
+        );
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table_valign(
+            &mut table,
+            &renderer,
+            VerticalAlignment::Bottom,
+        )?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{tabular}{>{\raggedleft\arraybackslash}b{7.5em}b{6.5em}}
+\hline
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+\hline
+Maecenas blandit elit. &  \\
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\hline
+\end{tabular}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_latex_without_header() -> crate::Result<()> {
+        let renderer = LatexBooktabs::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_header(false);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{tabular}{p{7.5em}p{6.5em}}
+\toprule
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+Maecenas blandit elit. &  \\
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\bottomrule
+\end{tabular}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_latex_decimal() {
+        let cells = ["1.5", "-12.25", "100"]
+            .into_iter()
+            .map(|s| vec![Cow::from(s)])
+            .collect();
+        let table = Table::from_vec(cells, 3).unwrap();
+        let mut renderer = Latex::default();
+        renderer.set_alignments(&[Alignment::Decimal], &[]);
+        assert_eq!(
+            renderer.render_table(&table, &[6]),
+            r#"\begin{tabular}{>{\raggedleft\arraybackslash}p{3.0em}}
+\hline
+1.5\phantom{0} \\
+-12.25 \\
+100\phantom{000} \\
+\hline
+\end{tabular}"#
+        );
+    }
+
+    #[test]
+    fn test_latex_booktabs() -> crate::Result<()> {
+        let renderer = LatexBooktabs::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{tabular}{p{7.5em}p{6.5em}}
+\toprule
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+\midrule
+Maecenas blandit elit. &  \\
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\bottomrule
+\end{tabular}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_latex_longtable() -> crate::Result<()> {
+        let renderer = LatexLongtable::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, wrapped_table) = fill_lipsum_table(&mut table, &renderer)?;
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{longtable}{p{7.5em}p{6.5em}}
+\hline
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+\hline
+\endhead
+Maecenas blandit elit. &  \\
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\hline
+\end{longtable}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_latex_longtable_with_footer() -> crate::Result<()> {
+        let renderer = LatexLongtable::default();
+        let mut table = read_lipsum_text()?;
+        let (widths, mut wrapped_table) =
+            fill_lipsum_table(&mut table, &renderer)?;
+        wrapped_table.set_footer(true);
+        let s = renderer.render_table(&wrapped_table, &widths);
+        assert_eq!(
+            s,
+            r#"\begin{longtable}{p{7.5em}p{6.5em}}
+\hline
+Duis facilisis. & Quisque ex nibh, auctor eu sodales. \\
+\hline
+\endhead
+\hline
+Sed lobortis, nibh vitae. & Mauris enim. \\
+\hline
+\endlastfoot
+Maecenas blandit elit. &  \\
+\end{longtable}"#
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,322 @@
+//! The title above a table and the caption below it.
+
+use crate::table::{render_to_string, Table, TableRenderer};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// Add a title and a caption to a table rendered by `inner`, both wrapped to
+/// the total width of the table. The title is centered above the table, or
+/// drawn inside the top border if the layout has one that fits the title.
+/// The caption is aligned to the left below the table. Since they're out of
+/// the table, the layout width is the same as that of `inner`. If `inner` is
+/// markup, both are rather left to it as the caption of the table.
+pub struct Titled<'a> {
+    inner: &'a dyn TableRenderer,
+    title: Option<&'a str>,
+    caption: Option<&'a str>,
+    /// The top border with the title inside, which replaces the top border
+    /// drawn by `inner`.
+    title_border: Option<String>,
+    title_lines: Vec<String>,
+    caption_lines: Vec<String>,
+}
+
+impl<'a> Titled<'a> {
+    /// Title and caption the tables rendered by `inner` given the widths of
+    /// each column, which must be the widths the tables are rendered at.
+    pub fn new(
+        inner: &'a dyn TableRenderer,
+        title: Option<&'a str>,
+        caption: Option<&'a str>,
+        widths: &[usize],
+    ) -> Self {
+        if inner.is_markup() {
+            return Self {
+                inner,
+                title,
+                caption,
+                title_border: None,
+                title_lines: vec![],
+                caption_lines: vec![],
+            };
+        }
+        let total_width =
+            widths.iter().sum::<usize>() + inner.layout_width(widths.len());
+        let title_border =
+            title.and_then(|title| inner.titled_top_border(title, widths));
+        let title_lines = match title {
+            Some(title) if title_border.is_none() => {
+                textwrap::wrap(title, total_width)
+                    .into_iter()
+                    .map(|line| {
+                        let line_width = textwrap::core::display_width(&line);
+                        let left_pad = total_width.saturating_sub(line_width);
+                        format!("{}{}", " ".repeat(left_pad / 2), line)
+                    })
+                    .collect()
+            }
+            _ => vec![],
+        };
+        let caption_lines = caption.map_or_else(Vec::new, |caption| {
+            textwrap::wrap(caption, total_width)
+                .into_iter()
+                .map(Cow::into_owned)
+                .collect()
+        });
+        Self {
+            inner,
+            title,
+            caption,
+            title_border,
+            title_lines,
+            caption_lines,
+        }
+    }
+}
+
+impl TableRenderer for Titled<'_> {
+    fn layout_width(&self, table_ncols: usize) -> usize {
+        self.inner.layout_width(table_ncols)
+    }
+
+    fn render_table(
+        &self,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> String {
+        render_to_string(self, filled_table, widths)
+    }
+
+    fn render_to(
+        &self,
+        w: &mut dyn Write,
+        filled_table: &Table<Vec<Cow<'_, str>>>,
+        widths: &[usize],
+    ) -> io::Result<()> {
+        if self.inner.is_markup() {
+            return self.inner.render_titled_to(
+                w,
+                filled_table,
+                widths,
+                self.title,
+                self.caption,
+            );
+        }
+        for line in self.title_lines.iter() {
+            w.write_all(line.as_bytes())?;
+            w.write_all(b"\n")?;
+        }
+        match &self.title_border {
+            None => self.inner.render_to(w, filled_table, widths)?,
+            Some(border) => {
+                let mut w = FirstLineReplacingWriter::new(w, border);
+                self.inner.render_to(&mut w, filled_table, widths)?;
+                w.finish()?;
+            }
+        }
+        for line in self.caption_lines.iter() {
+            w.write_all(b"\n")?;
+            w.write_all(line.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn has_header_by_default(&self) -> bool {
+        self.inner.has_header_by_default()
+    }
+
+    fn supports_ansi_styles(&self) -> bool {
+        self.inner.supports_ansi_styles()
+    }
+
+    fn is_markup(&self) -> bool {
+        self.inner.is_markup()
+    }
+
+    fn rewraps_text(&self) -> bool {
+        self.inner.rewraps_text()
+    }
+
+    fn rendered_height(
+        &self,
+        row_heights: &[usize],
+        has_header: bool,
+        has_footer: bool,
+    ) -> Option<usize> {
+        self.inner
+            .rendered_height(row_heights, has_header, has_footer)
+            .map(|h| h + self.title_lines.len() + self.caption_lines.len())
+    }
+}
+
+/// A writer that replaces the first line written to `inner` by `first_line`.
+/// Call [`finish`](FirstLineReplacingWriter::finish) to write `first_line`
+/// if no complete line has been written.
+struct FirstLineReplacingWriter<'a> {
+    inner: &'a mut dyn Write,
+    /// `None` once the first line is replaced.
+    first_line: Option<&'a str>,
+}
+
+impl<'a> FirstLineReplacingWriter<'a> {
+    fn new(inner: &'a mut dyn Write, first_line: &'a str) -> Self {
+        Self {
+            inner,
+            first_line: Some(first_line),
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(first_line) = self.first_line.take() {
+            self.inner.write_all(first_line.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for FirstLineReplacingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.first_line {
+            None => self.inner.write_all(buf)?,
+            Some(first_line) => {
+                // Drop the first line up to the newline.
+                if let Some(i) = buf.iter().position(|&b| b == b'\n') {
+                    self.inner.write_all(first_line.as_bytes())?;
+                    self.inner.write_all(&buf[i..])?;
+                    self.first_line = None;
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Titled;
+    use crate::styles::{Color, StyleOptions, Styled};
+    use crate::table::{new_filled_table, TableRenderer};
+    use crate::table_renderers::{
+        Grid, Html, Latex, LatexLongtable, SimpleGrid,
+    };
+
+    #[test]
+    fn test_titled_grid() {
+        let table = new_filled_table(2, 2, false);
+        let renderer =
+            Titled::new(&Grid, Some("Title"), Some("A caption"), &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]),
+            "   Title\n\
+             +----+----+\n\
+             | ab | cd |\n\
+             +----+----+\n\
+             | ef | gh |\n\
+             +----+----+\n\
+             A caption"
+        );
+        assert_eq!(renderer.rendered_height(&[1, 1], false, false), Some(7));
+    }
+
+    #[test]
+    fn test_titled_simple_grid() {
+        let table = new_filled_table(2, 2, false);
+        let renderer = Titled::new(&SimpleGrid, Some("Title"), None, &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]),
+            "┌─ Title ─┐\n\
+             │ ab │ cd │\n\
+             ├────┼────┤\n\
+             │ ef │ gh │\n\
+             └────┴────┘"
+        );
+        assert_eq!(renderer.rendered_height(&[1, 1], false, false), Some(5));
+        // Too long to fit in the top border.
+        let renderer =
+            Titled::new(&SimpleGrid, Some("Long title"), None, &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]).lines().next(),
+            Some("Long title")
+        );
+        // Wrapped to the table width.
+        let renderer = Titled::new(
+            &SimpleGrid,
+            Some("A much longer title"),
+            None,
+            &[2, 2],
+        );
+        assert_eq!(
+            renderer
+                .render_table(&table, &[2, 2])
+                .lines()
+                .take(4)
+                .collect::<Vec<_>>(),
+            vec!["  A much", "  longer", "   title", "┌────┬────┐"]
+        );
+        assert_eq!(renderer.rendered_height(&[1, 1], false, false), Some(8));
+    }
+
+    #[test]
+    fn test_titled_html() {
+        let table = new_filled_table(2, 2, false);
+        let html = Html::default();
+        let renderer =
+            Titled::new(&html, Some("Title"), Some("A & B"), &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]),
+            "<table>\n\
+             <caption>Title<br>A &amp; B</caption>\n\
+             <tbody>\n\
+             <tr><td>ab</td><td>cd</td></tr>\n\
+             <tr><td>ef</td><td>gh</td></tr>\n\
+             </tbody>\n\
+             </table>"
+        );
+        assert_eq!(renderer.rendered_height(&[1, 1], false, false), None);
+    }
+
+    #[test]
+    fn test_titled_latex() {
+        let table = new_filled_table(2, 2, false);
+        let latex = Latex::default();
+        let renderer = Titled::new(&latex, Some("Title"), None, &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]),
+            "\\begin{table}\n\
+             \\caption{Title}\n\
+             \\begin{tabular}{p{1.0em}p{1.0em}}\n\
+             \\hline\n\
+             ab & cd \\\\\n\
+             ef & gh \\\\\n\
+             \\hline\n\
+             \\end{tabular}\n\
+             \\end{table}"
+        );
+        let longtable = LatexLongtable::default();
+        let renderer =
+            Titled::new(&longtable, Some("Title"), Some("A & B"), &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]).lines().nth(1),
+            Some("\\caption{Title A \\& B} \\\\")
+        );
+    }
+
+    #[test]
+    fn test_titled_styled_border() {
+        let table = new_filled_table(2, 2, false);
+        let opts = StyleOptions {
+            border_color: Some(Color::Red),
+            ..StyleOptions::default()
+        };
+        let styled = Styled::new(SimpleGrid, opts);
+        let renderer = Titled::new(&styled, Some("T"), None, &[2, 2]);
+        assert_eq!(
+            renderer.render_table(&table, &[2, 2]).lines().next(),
+            Some("\x1b[31m┌─ T ┬────┐\x1b[0m")
+        );
+    }
+}
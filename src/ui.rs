@@ -1,10 +1,30 @@
-use crate::io::ReadOptions;
+use crate::aggregate::Aggregate;
+use crate::column_planner::{
+    Fallback, MinWidthCause, Objective, PlanOptions, Sizing, TieBreak,
+    WidthConstraint, WidthRange,
+};
+use crate::io::{InputFormat, ReadOptions};
+use crate::styles::{Color, ColorChoice, HeaderStyle, StyleOptions};
+use crate::table::{Alignment, VerticalAlignment, DEFAULT_ELLIPSIS};
 use clap::Parser;
 use std::fmt::{Display, Formatter};
 use std::process::ExitCode;
 
 #[derive(Debug, Clone)]
-pub struct UserWidths(Vec<Option<usize>>);
+pub struct UserWidths(Vec<WidthConstraint>);
+
+/// The largest fraction of a fractional width, far beyond any table width.
+const MAX_FRACTION: usize = 10000;
+
+/// Parse an optional width bound of a width range.
+fn parse_width_bound(s: &str) -> Result<Option<usize>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.parse()
+        .map(Some)
+        .map_err(|_| format!("width `{}` is not a nonnegative integer", s))
+}
 
 impl TryFrom<Option<String>> for UserWidths {
     type Error = String;
@@ -14,9 +34,43 @@ impl TryFrom<Option<String>> for UserWidths {
         if let Some(value) = value {
             for s in value.split(',') {
                 let uw = if s == "*" {
-                    None
+                    WidthConstraint::ANY
+                } else if let Some((min, max)) = s.split_once("..") {
+                    let min = parse_width_bound(min)?.unwrap_or(0);
+                    let max = parse_width_bound(max)?;
+                    if max.is_some_and(|max| min > max) {
+                        return Err(format!(
+                            "width range `{}` has minimum greater than maximum",
+                            s
+                        ));
+                    }
+                    WidthConstraint::Range(WidthRange { min, max })
+                } else if let Some(percent) = s.strip_suffix('%') {
+                    match percent.parse() {
+                        Ok(percent) if percent <= 100 => {
+                            WidthConstraint::Percent(percent)
+                        }
+                        _ => return Err(format!(
+                            "width `{}` is not a percentage within 0%..100%",
+                            s
+                        )),
+                    }
+                } else if let Some(fraction) = s.strip_suffix("fr") {
+                    match fraction.parse() {
+                        Ok(fraction)
+                            if (1..=MAX_FRACTION).contains(&fraction) =>
+                        {
+                            WidthConstraint::Fraction(fraction)
+                        }
+                        _ => {
+                            return Err(format!(
+                                "width `{}` is not a fraction in 1fr..{}fr",
+                                s, MAX_FRACTION
+                            ))
+                        }
+                    }
                 } else {
-                    Some(s.parse().map_err(|_| {
+                    WidthConstraint::Fixed(s.parse().map_err(|_| {
                         format!("width `{}` is not a nonnegative integer", s)
                     })?)
                 };
@@ -28,7 +82,12 @@ impl TryFrom<Option<String>> for UserWidths {
 }
 
 impl UserWidths {
-    pub fn into_vec(self, ncols: usize) -> Vec<Option<usize>> {
+    /// Return `true` if no width is specified.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_vec(self, ncols: usize) -> Vec<WidthConstraint> {
         let mut user_widths = self.0;
         let len = user_widths.len();
         if len < ncols {
@@ -38,7 +97,8 @@ impl UserWidths {
             if len > 0 {
                 eprintln!("W: Padding USER_WIDTHS with `*`");
             }
-            user_widths.extend(std::iter::repeat(None).take(n_rest));
+            user_widths
+                .extend(std::iter::repeat_n(WidthConstraint::ANY, n_rest));
         } else if len > ncols {
             eprintln!("W: Truncating USER_WIDTHS to ncols={}", ncols);
             user_widths.truncate(ncols);
@@ -47,11 +107,407 @@ impl UserWidths {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct UserAlignments(Vec<Alignment>);
+
+impl TryFrom<Option<String>> for UserAlignments {
+    type Error = String;
+
+    fn try_from(value: Option<String>) -> Result<Self, Self::Error> {
+        let mut alignments = vec![];
+        if let Some(value) = value {
+            for s in value.split(',') {
+                let alignment = match s {
+                    "l" => Alignment::Left,
+                    "r" => Alignment::Right,
+                    "c" => Alignment::Center,
+                    "." => Alignment::Decimal,
+                    _ => {
+                        return Err(format!(
+                        "alignment `{}` is not one of `l`, `r`, `c` and `.`",
+                        s
+                    ))
+                    }
+                };
+                alignments.push(alignment);
+            }
+        }
+        Ok(Self(alignments))
+    }
+}
+
+impl UserAlignments {
+    pub fn into_vec(self, ncols: usize) -> Vec<Alignment> {
+        let mut alignments = self.0;
+        let len = alignments.len();
+        if len < ncols {
+            alignments
+                .extend(std::iter::repeat_n(Alignment::Left, ncols - len));
+        } else if len > ncols {
+            eprintln!("W: Truncating ALIGNMENTS to ncols={}", ncols);
+            alignments.truncate(ncols);
+        }
+        alignments
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserVerticalAlignments(Vec<VerticalAlignment>);
+
+impl TryFrom<Option<String>> for UserVerticalAlignments {
+    type Error = String;
+
+    fn try_from(value: Option<String>) -> Result<Self, Self::Error> {
+        let mut vertical_alignments = vec![];
+        if let Some(value) = value {
+            for s in value.split(',') {
+                let vertical_alignment = match s {
+                    "t" => VerticalAlignment::Top,
+                    "m" => VerticalAlignment::Middle,
+                    "b" => VerticalAlignment::Bottom,
+                    _ => return Err(format!("vertical alignment `{}` is not one of `t`, `m` and `b`", s)),
+                };
+                vertical_alignments.push(vertical_alignment);
+            }
+        }
+        Ok(Self(vertical_alignments))
+    }
+}
+
+impl UserVerticalAlignments {
+    /// A single vertical alignment applies to every column. Otherwise, the
+    /// unspecified columns are top-aligned.
+    pub fn into_vec(self, ncols: usize) -> Vec<VerticalAlignment> {
+        let mut vertical_alignments = self.0;
+        let len = vertical_alignments.len();
+        if len == 1 {
+            vertical_alignments = vec![vertical_alignments[0]; ncols];
+        } else if len < ncols {
+            vertical_alignments.extend(std::iter::repeat_n(
+                VerticalAlignment::Top,
+                ncols - len,
+            ));
+        } else if len > ncols {
+            eprintln!("W: Truncating VALIGNMENTS to ncols={}", ncols);
+            vertical_alignments.truncate(ncols);
+        }
+        vertical_alignments
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserMaxLines(Vec<Option<usize>>);
+
+impl TryFrom<Option<String>> for UserMaxLines {
+    type Error = String;
+
+    fn try_from(value: Option<String>) -> Result<Self, Self::Error> {
+        let mut max_lines = vec![];
+        if let Some(value) = value {
+            for s in value.split(',') {
+                let ml = if s == "*" {
+                    None
+                } else {
+                    match s.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            return Err(format!(
+                                "max lines `{}` is not a positive integer",
+                                s
+                            ))
+                        }
+                    }
+                };
+                max_lines.push(ml);
+            }
+        }
+        Ok(Self(max_lines))
+    }
+}
+
+impl UserMaxLines {
+    /// A single value applies to every column. Otherwise, the unspecified
+    /// columns are unlimited.
+    pub fn into_vec(self, ncols: usize) -> Vec<Option<usize>> {
+        let mut max_lines = self.0;
+        let len = max_lines.len();
+        if len == 1 {
+            max_lines = vec![max_lines[0]; ncols];
+        } else if len < ncols {
+            max_lines.extend(std::iter::repeat_n(None, ncols - len));
+        } else if len > ncols {
+            eprintln!("W: Truncating MAX_LINES to ncols={}", ncols);
+            max_lines.truncate(ncols);
+        }
+        max_lines
+    }
+}
+
+/// How to split the words of a column into smaller pieces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splitter {
+    /// Never split the words.
+    None,
+    /// Split the words at their hyphens.
+    Hyphen,
+    /// Hyphenate the words by the dictionary of `--hyphenate`.
+    Dictionary,
+}
+
+/// The wrap options of each column, from `--indent`, `--break-words` and
+/// `--split`. A single value applies to every column. Otherwise, the
+/// unspecified columns follow the shared wrap options.
+#[derive(Debug, Clone, Default)]
+pub struct UserColumnWraps {
+    /// The hanging indent of each column.
+    indents: Vec<String>,
+    break_words: Vec<bool>,
+    splitters: Vec<Splitter>,
+}
+
+impl UserColumnWraps {
+    pub fn parse(
+        indents: Option<String>,
+        break_words: Option<String>,
+        splitters: Option<String>,
+    ) -> Result<Self, String> {
+        let mut column_wraps = Self::default();
+        if let Some(indents) = indents {
+            for s in indents.split(',') {
+                let Ok(n) = s.parse::<usize>() else {
+                    return Err(format!(
+                        "indent `{}` is not a non-negative integer",
+                        s
+                    ));
+                };
+                column_wraps.indents.push(" ".repeat(n));
+            }
+        }
+        if let Some(break_words) = break_words {
+            for s in break_words.split(',') {
+                let b = match s {
+                    "y" => true,
+                    "n" => false,
+                    _ => {
+                        return Err(format!(
+                            "break policy `{}` is not one of `y` and `n`",
+                            s
+                        ))
+                    }
+                };
+                column_wraps.break_words.push(b);
+            }
+        }
+        if let Some(splitters) = splitters {
+            for s in splitters.split(',') {
+                let splitter = match s {
+                    "n" => Splitter::None,
+                    "h" => Splitter::Hyphen,
+                    "d" => Splitter::Dictionary,
+                    _ => {
+                        return Err(format!(
+                            "splitter `{}` is not one of `n`, `h` and `d`",
+                            s
+                        ))
+                    }
+                };
+                column_wraps.splitters.push(splitter);
+            }
+        }
+        Ok(column_wraps)
+    }
+
+    /// Whether some column is hyphenated by the dictionary.
+    pub fn uses_dictionary(&self) -> bool {
+        self.splitters.contains(&Splitter::Dictionary)
+    }
+
+    /// Derive the options of the columns of a table with `ncols` columns
+    /// from the `shared` options, `None` for the columns that follow them.
+    pub fn to_options<'a>(
+        &'a self,
+        shared: &textwrap::Options<'a>,
+        ncols: usize,
+    ) -> Vec<Option<textwrap::Options<'a>>> {
+        for (name, len) in [
+            ("INDENTS", self.indents.len()),
+            ("BREAK_WORDS", self.break_words.len()),
+            ("SPLITTERS", self.splitters.len()),
+        ] {
+            if len > ncols {
+                eprintln!("W: Truncating {} to ncols={}", name, ncols);
+            }
+        }
+        (0..ncols)
+            .map(|j| {
+                let indent = per_column(&self.indents, j);
+                let break_words = per_column(&self.break_words, j);
+                let splitter = per_column(&self.splitters, j);
+                if indent.is_none()
+                    && break_words.is_none()
+                    && splitter.is_none()
+                {
+                    return None;
+                }
+                let mut opts = shared.clone();
+                if let Some(indent) = indent {
+                    opts.subsequent_indent = indent;
+                }
+                if let Some(break_words) = break_words {
+                    opts.break_words = *break_words;
+                }
+                match splitter {
+                    Some(Splitter::None) => {
+                        opts.word_splitter =
+                            textwrap::WordSplitter::NoHyphenation;
+                    }
+                    Some(Splitter::Hyphen) => {
+                        opts.word_splitter =
+                            textwrap::WordSplitter::HyphenSplitter;
+                    }
+                    // The shared splitter is the dictionary.
+                    Some(Splitter::Dictionary) | None => (),
+                }
+                Some(opts)
+            })
+            .collect()
+    }
+}
+
+/// Get the value of the `col_idx`-th column, where a single value applies
+/// to every column.
+fn per_column<T>(values: &[T], col_idx: usize) -> Option<&T> {
+    if values.len() == 1 {
+        values.first()
+    } else {
+        values.get(col_idx)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserKeyColumns(Vec<usize>);
+
+impl TryFrom<Option<String>> for UserKeyColumns {
+    type Error = String;
+
+    fn try_from(value: Option<String>) -> Result<Self, Self::Error> {
+        let mut key_columns = vec![];
+        if let Some(value) = value {
+            for s in value.split(',') {
+                // Columns are numbered from 1 as in the error messages.
+                match s.parse::<usize>() {
+                    Ok(j) if j > 0 => key_columns.push(j - 1),
+                    _ => {
+                        return Err(format!(
+                            "key column `{}` is not a positive integer",
+                            s
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(Self(key_columns))
+    }
+}
+
+impl UserKeyColumns {
+    pub fn into_vec(self, ncols: usize) -> Vec<usize> {
+        let mut key_columns = vec![];
+        for j in self.0 {
+            if j >= ncols {
+                eprintln!("W: Ignoring key column {} > ncols={}", j + 1, ncols);
+            } else if !key_columns.contains(&j) {
+                key_columns.push(j);
+            }
+        }
+        key_columns
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserFooter(Vec<(usize, Aggregate)>);
+
+impl TryFrom<Option<String>> for UserFooter {
+    type Error = String;
+
+    fn try_from(value: Option<String>) -> Result<Self, Self::Error> {
+        let mut aggregates: Vec<(usize, Aggregate)> = vec![];
+        if let Some(value) = value {
+            for s in value.split(',') {
+                let (name, col) = s.split_once(':').ok_or_else(|| {
+                    format!("aggregate `{}` is not of the form `AGG:COL`", s)
+                })?;
+                let aggregate = Aggregate::from_name(name)
+                    .ok_or_else(|| format!("invalid aggregate `{}`", name))?;
+                let j = match col.parse::<usize>() {
+                    Ok(j) if j > 0 => j - 1,
+                    _ => {
+                        return Err(format!(
+                            "aggregate column `{}` is not a positive integer",
+                            col
+                        ))
+                    }
+                };
+                if aggregates.iter().any(|(k, _)| *k == j) {
+                    return Err(format!(
+                        "column {} has more than one aggregate",
+                        col
+                    ));
+                }
+                aggregates.push((j, aggregate));
+            }
+        }
+        Ok(Self(aggregates))
+    }
+}
+
+impl UserFooter {
+    pub fn into_vec(self, ncols: usize) -> Vec<(usize, Aggregate)> {
+        let mut aggregates = vec![];
+        for (j, aggregate) in self.0 {
+            if j >= ncols {
+                eprintln!(
+                    "W: Ignoring aggregate of column {} > ncols={}",
+                    j + 1,
+                    ncols
+                );
+            } else {
+                aggregates.push((j, aggregate));
+            }
+        }
+        aggregates
+    }
+}
+
+/// When to print each row as a block of key/value pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpandedMode {
+    #[default]
+    Off,
+    On,
+    /// Expand only if the table can't fit in the table width otherwise.
+    Auto,
+}
+
+impl ExpandedMode {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(Self::Off),
+            "on" => Some(Self::On),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+}
+
 /// Format plain text into fixed-width table with multi-line cell by wrapping
 /// text in each field.
 #[derive(Parser, Debug)]
 pub struct Cli {
-    /// The column widths.
+    /// The column widths, each one of a width, `*` (decided automatically),
+    /// a range `MIN..MAX` within which the width is decided, where either
+    /// bound may be omitted, a percentage `N%` of the table width, or a
+    /// fraction `Nfr` of the width left by the other columns.
     #[arg(short = 'W', long = "widths", name = "WIDTHS")]
     user_widths: Option<String>,
     /// The table total width, default to terminal width.
@@ -60,15 +516,180 @@ pub struct Cli {
     /// The table layout, default to "grid_no_header".
     #[arg(short = 'L', long = "layout")]
     table_layout: Option<String>,
+    /// Specify to take the first row as the header, which is the default of
+    /// the keys of JSON objects, and of "grid", "simple", "github",
+    /// "fancy_grid" and the "html*" and "latex*" layouts.
+    #[arg(long = "header", overrides_with = "no_header")]
+    header: bool,
+    /// Specify to take the first row as an ordinary row.
+    #[arg(long = "no-header", overrides_with = "header")]
+    no_header: bool,
+    /// The names of the columns, e.g. `a,b,c`, which replace the header, or
+    /// make up a new header if the first row is not a header. Implies
+    /// `--header`.
+    #[arg(long = "header-names", name = "NAMES")]
+    header_names: Option<String>,
+    /// Whether to break long words in the header, one of `y` and `n`,
+    /// regardless of `--break-words`.
+    #[arg(long = "header-break-words", name = "FLAG")]
+    header_break_words: Option<String>,
+    /// The aggregates in the footer row, each one of `AGG:COL`, where AGG is
+    /// one of "sum", "mean", "min", "max" and "count", and COL is a column
+    /// numbered from 1, e.g. `sum:3,mean:4`. The cells that are not numbers
+    /// are skipped.
+    #[arg(long = "footer", name = "AGGREGATES")]
+    footer: Option<String>,
+    /// The title centered above the table, or drawn inside the top border in
+    /// the box layouts, e.g. "simple_grid". In the "html*" and "latex*"
+    /// layouts, the title begins the caption of the table, e.g. `<caption>`.
+    #[arg(long = "title", name = "TITLE")]
+    title: Option<String>,
+    /// The caption below the table. In the "html*" and "latex*" layouts, the
+    /// caption ends the caption of the table after the title, if any.
+    #[arg(long = "caption", name = "CAPTION")]
+    caption: Option<String>,
+    /// The column alignments, each one of `l` (left), `r` (right), `c`
+    /// (center) and `.` (decimal point), default to `l`.
+    #[arg(short = 'A', long = "align", name = "ALIGNMENTS")]
+    alignments: Option<String>,
+    /// The column vertical alignments, each one of `t` (top), `m` (middle)
+    /// and `b` (bottom), default to `t`. A single value applies to all
+    /// columns.
+    #[arg(short = 'V', long = "valign", name = "VALIGNMENTS")]
+    vertical_alignments: Option<String>,
+    /// The objective of deciding the column widths, one of "lines" (the total
+    /// number of lines), "max-row-height" (the height of the tallest row),
+    /// "weighted:W1,W2,..." (the total number of lines where the i-th row
+    /// counts Wi times, and the rest rows count once) and "area" (the total
+    /// number of lines times the table width, where the table may be
+    /// narrower than the table width), default to "lines".
+    #[arg(long = "objective", name = "OBJECTIVE")]
+    objective: Option<String>,
+    /// Which column widths to prefer among those of the same objective
+    /// value, one of "none", "balanced" (the widths closest to each other)
+    /// and "monotone" (a column of longer text is not narrower than a column
+    /// of shorter text), default to "none".
+    #[arg(long = "tie-break", name = "TIE_BREAK")]
+    tie_break: Option<String>,
+    /// How to size the columns, one of "fill" (use up the table width) and
+    /// "compact" (use the widths of the longest lines if they fit in the
+    /// table width, and wrap the columns only as needed otherwise), default
+    /// to "fill".
+    #[arg(long = "sizing", name = "SIZING")]
+    sizing: Option<String>,
+    /// Specify to shrink the table to the smallest width at which the
+    /// objective value is at most TOLERANCE more than that at the table
+    /// width, e.g. `--shrink=5%`, default to `0%`.
+    #[arg(
+        long = "shrink",
+        name = "TOLERANCE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "0%"
+    )]
+    shrink_tolerance: Option<String>,
+    /// Print each row as a block of key/value pairs, where the keys are the
+    /// header, or the column numbers if there's no header, one of "off", "on"
+    /// and "auto", default to "off". In "auto" mode, the table is expanded
+    /// only if it can't fit in the table width otherwise.
+    #[arg(short = 'x', long = "expanded", name = "WHEN_EXPANDED")]
+    expanded_mode: Option<String>,
+    /// Specify to split the columns into pages, each rendered as a table of
+    /// its own, if they can't fit in the table width altogether.
+    #[arg(long = "column-pages", default_value_t = false)]
+    column_pages: bool,
+    /// The columns to repeat on every page of columns, numbered from 1,
+    /// e.g. `1,2`.
+    #[arg(long = "key-columns", name = "KEY_COLUMNS")]
+    key_columns: Option<String>,
+    /// The maximum number of lines per page of rows, default to the terminal
+    /// height. The header is repeated on every page. Specify 0 to disable
+    /// pagination.
+    #[arg(long = "page-height", name = "PAGE_HEIGHT")]
+    page_height: Option<usize>,
+    /// The maximum number of lines of the cells in each column, where `*`
+    /// means unlimited. A single value applies to all columns. Longer cells
+    /// are truncated with an ellipsis.
+    #[arg(long = "max-lines", name = "MAX_LINES")]
+    max_lines: Option<String>,
+    /// Specify to truncate every cell to a single line instead of wrapping
+    /// it, same as `--max-lines 1`.
+    #[arg(
+        long = "no-wrap",
+        default_value_t = false,
+        conflicts_with = "MAX_LINES"
+    )]
+    no_wrap: bool,
+    /// The marker at the end of truncated cells, default to `…`.
+    #[arg(long = "ellipsis", name = "ELLIPSIS")]
+    ellipsis: Option<String>,
+    /// The algorithm to wrap the words into lines, one of "first-fit" and
+    /// "optimal-fit", default to "optimal-fit".
+    #[arg(long = "wrap-algorithm", name = "ALGO")]
+    wrap_algorithm: Option<String>,
+    /// How to find the words in text, one of "ascii" (split at spaces) and
+    /// "unicode" (split by the Unicode line breaking algorithm), default to
+    /// "unicode".
+    #[arg(long = "word-separator", name = "SEP")]
+    word_separator: Option<String>,
+    /// Hyphenate long words by the dictionary of the language, e.g. "en-us".
+    /// Requires the "hyphenation" feature.
+    #[arg(long = "hyphenate", name = "LANG")]
+    hyphenate: Option<String>,
+    /// The hanging indent of the wrapped lines in each column, e.g. `0,2`,
+    /// default to 0. A single value applies to all columns.
+    #[arg(long = "indent", name = "INDENTS")]
+    indents: Option<String>,
+    /// Whether to break long words in each column, each one of `y` and `n`,
+    /// default to `n`. A single value applies to all columns.
+    #[arg(long = "break-words", name = "BREAK_WORDS")]
+    break_words: Option<String>,
+    /// How to split the words in each column, each one of `n` (never), `h`
+    /// (at hyphens) and `d` (by the dictionary of `--hyphenate`), default to
+    /// `d` with `--hyphenate`, or `h` otherwise. A single value applies to
+    /// all columns.
+    #[arg(long = "split", name = "SPLITTERS")]
+    splitters: Option<String>,
+    /// What to do if some words are longer than their column widths, one of
+    /// "error", "overflow" (let the words overflow the columns whose widths
+    /// are specified) and "break" (break the words in the columns that can't
+    /// fit them otherwise), default to "overflow". "error" implies `-S`.
+    #[arg(long = "fallback", name = "FALLBACK")]
+    fallback: Option<String>,
     /// Specify to enable strict mode.
     #[arg(short = 'S', long, default_value_t = false)]
     strict: bool,
-    /// The field delimiter in the input data, default to <TAB>.
+    /// The input format, one of "delimited", "csv", "json" and "jsonl",
+    /// default to "delimited".
+    #[arg(short = 'f', long = "format", name = "FORMAT")]
+    input_format: Option<String>,
+    /// The field delimiter in the input data, default to <TAB>, or to `,`
+    /// if the input format is "csv".
     #[arg(short = 'd', long = "delimiter", name = "DELIMITER")]
     field_delimiter: Option<String>,
+    /// The quote character in "csv" input data, default to `"`.
+    #[arg(short = 'q', long = "quote", name = "QUOTE")]
+    quote_char: Option<char>,
     /// Specify to enable escape sequence as `echo -e` in input data.
     #[arg(short = 'e', long = "escape", default_value_t = false)]
     enable_escape_sequence: bool,
+    /// When to style the output, one of "auto", "always" and "never",
+    /// default to "auto", which styles the output only if stdout is a
+    /// terminal and `NO_COLOR` is not set.
+    #[arg(long = "color", name = "WHEN")]
+    color_choice: Option<String>,
+    /// The color of the borders, one of "black", "red", "green", "yellow",
+    /// "blue", "magenta", "cyan" and "white".
+    #[arg(long = "border-color", name = "COLOR")]
+    border_color: Option<String>,
+    /// The style of the header row, one of "none", "bold" and "inverse",
+    /// default to "none". Only tables with a header are affected.
+    #[arg(long = "header-style", name = "STYLE")]
+    header_style: Option<String>,
+    /// The background color of every other row. See `--border-color` for
+    /// the available colors.
+    #[arg(long = "zebra", name = "ZEBRA_COLOR")]
+    zebra_color: Option<String>,
     /// The input stream, default to stdin.
     filename: Option<String>,
 }
@@ -78,11 +699,71 @@ pub struct PostCli {
     pub user_widths: UserWidths,
     pub user_total_width: Option<usize>,
     pub table_layout: String,
+    /// `None` if the first row is a header as the input or the layout says.
+    pub header: Option<bool>,
+    pub header_names: Option<Vec<String>>,
+    /// `None` if the header is wrapped as the other rows.
+    pub header_break_words: Option<bool>,
+    pub footer: UserFooter,
+    pub title: Option<String>,
+    pub caption: Option<String>,
+    pub alignments: UserAlignments,
+    pub vertical_alignments: UserVerticalAlignments,
+    pub plan_opts: PlanOptions,
+    pub expanded_mode: ExpandedMode,
+    pub column_pages: bool,
+    pub key_columns: UserKeyColumns,
+    pub max_lines: UserMaxLines,
+    pub ellipsis: String,
+    pub wrap_algorithm: textwrap::WrapAlgorithm,
+    pub word_separator: textwrap::WordSeparator,
+    pub word_splitter: textwrap::WordSplitter,
+    pub column_wraps: UserColumnWraps,
+    /// `None` if the rows should not be paginated.
+    pub page_height: Option<usize>,
     pub strict: bool,
     pub read_opts: ReadOptions,
+    /// `None` if the output should not be styled.
+    pub style_opts: Option<StyleOptions>,
     pub filename: Option<String>,
 }
 
+fn parse_wrap_algorithm(name: &str) -> Result<textwrap::WrapAlgorithm, String> {
+    match name {
+        "first-fit" => Ok(textwrap::WrapAlgorithm::FirstFit),
+        "optimal-fit" => Ok(textwrap::WrapAlgorithm::new_optimal_fit()),
+        _ => Err(format!("invalid wrap algorithm `{}`", name)),
+    }
+}
+
+fn parse_word_separator(name: &str) -> Result<textwrap::WordSeparator, String> {
+    match name {
+        "ascii" => Ok(textwrap::WordSeparator::AsciiSpace),
+        "unicode" => Ok(textwrap::WordSeparator::UnicodeBreakProperties),
+        _ => Err(format!("invalid word separator `{}`", name)),
+    }
+}
+
+#[cfg(feature = "hyphenation")]
+fn parse_hyphenation(lang: &str) -> Result<textwrap::WordSplitter, String> {
+    use hyphenation::{Language, Load, Standard};
+
+    let language = Language::try_from_code(lang)
+        .ok_or_else(|| format!("invalid hyphenation language `{}`", lang))?;
+    let dictionary = Standard::from_embedded(language)
+        .map_err(|err| format!("failed to load dictionary: {}", err))?;
+    Ok(textwrap::WordSplitter::Hyphenation(dictionary))
+}
+
+#[cfg(not(feature = "hyphenation"))]
+fn parse_hyphenation(_lang: &str) -> Result<textwrap::WordSplitter, String> {
+    Err("hyphenation requires the \"hyphenation\" feature".into())
+}
+
+fn parse_color(name: &str) -> Result<Color, String> {
+    Color::from_name(name).ok_or_else(|| format!("invalid color `{}`", name))
+}
+
 impl Cli {
     pub fn parse_and_validate() -> Result<PostCli, ExitCode> {
         let cli = Self::parse();
@@ -92,19 +773,223 @@ impl Cli {
                 ExitCode::from(1)
             })?;
         let table_layout = cli.table_layout.unwrap_or("grid_no_header".into());
+        let header = match (cli.header, cli.no_header) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
+        let header_names = cli
+            .header_names
+            .map(|names| names.split(',').map(String::from).collect());
+        let header_break_words = match cli.header_break_words.as_deref() {
+            None => None,
+            Some("y") => Some(true),
+            Some("n") => Some(false),
+            Some(s) => {
+                eprintln!(
+                    "E: header break policy `{}` is not one of `y` and `n`",
+                    s
+                );
+                return Err(ExitCode::from(1));
+            }
+        };
+        let footer = UserFooter::try_from(cli.footer).map_err(|msg| {
+            eprintln!("E: {}", msg);
+            ExitCode::from(1)
+        })?;
+        let alignments =
+            UserAlignments::try_from(cli.alignments).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?;
+        let vertical_alignments = UserVerticalAlignments::try_from(
+            cli.vertical_alignments,
+        )
+        .map_err(|msg| {
+            eprintln!("E: {}", msg);
+            ExitCode::from(1)
+        })?;
+        let mut plan_opts = PlanOptions::default();
+        if let Some(name) = cli.objective {
+            plan_opts.objective =
+                Objective::from_name(&name).ok_or_else(|| {
+                    eprintln!("E: invalid objective `{}`", name);
+                    ExitCode::from(1)
+                })?;
+        }
+        if let Some(name) = cli.tie_break {
+            plan_opts.tie_break =
+                TieBreak::from_name(&name).ok_or_else(|| {
+                    eprintln!("E: invalid tie-break `{}`", name);
+                    ExitCode::from(1)
+                })?;
+        }
+        if let Some(name) = cli.sizing {
+            plan_opts.sizing = Sizing::from_name(&name).ok_or_else(|| {
+                eprintln!("E: invalid sizing `{}`", name);
+                ExitCode::from(1)
+            })?;
+        }
+        if let Some(name) = cli.fallback {
+            plan_opts.fallback =
+                Fallback::from_name(&name).ok_or_else(|| {
+                    eprintln!("E: invalid fallback `{}`", name);
+                    ExitCode::from(1)
+                })?;
+        }
+        let strict = cli.strict || plan_opts.fallback == Fallback::Error;
+        if let Some(tolerance) = cli.shrink_tolerance {
+            let percent = tolerance.strip_suffix('%').unwrap_or(&tolerance);
+            plan_opts.shrink_tolerance =
+                Some(percent.parse().map_err(|_| {
+                    eprintln!("E: invalid tolerance `{}`", tolerance);
+                    ExitCode::from(1)
+                })?);
+        }
+        let expanded_mode = match cli.expanded_mode {
+            None => ExpandedMode::default(),
+            Some(name) => ExpandedMode::from_name(&name).ok_or_else(|| {
+                eprintln!("E: invalid expanded mode `{}`", name);
+                ExitCode::from(1)
+            })?,
+        };
+        let key_columns =
+            UserKeyColumns::try_from(cli.key_columns).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?;
+        let max_lines = if cli.no_wrap {
+            UserMaxLines(vec![Some(1)])
+        } else {
+            UserMaxLines::try_from(cli.max_lines).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?
+        };
+        let ellipsis = cli.ellipsis.unwrap_or(DEFAULT_ELLIPSIS.into());
+        let wrap_algorithm = match cli.wrap_algorithm {
+            None => textwrap::WrapAlgorithm::new(),
+            Some(name) => parse_wrap_algorithm(&name).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?,
+        };
+        let word_separator = match cli.word_separator {
+            None => textwrap::WordSeparator::UnicodeBreakProperties,
+            Some(name) => parse_word_separator(&name).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?,
+        };
+        let hyphenate = cli.hyphenate.is_some();
+        let word_splitter = match cli.hyphenate {
+            None => textwrap::WordSplitter::HyphenSplitter,
+            Some(lang) => parse_hyphenation(&lang).map_err(|msg| {
+                eprintln!("E: {}", msg);
+                ExitCode::from(1)
+            })?,
+        };
+        let column_wraps =
+            UserColumnWraps::parse(cli.indents, cli.break_words, cli.splitters)
+                .map_err(|msg| {
+                    eprintln!("E: {}", msg);
+                    ExitCode::from(1)
+                })?;
+        if column_wraps.uses_dictionary() && !hyphenate {
+            eprintln!("E: splitter `d` requires `--hyphenate`");
+            return Err(ExitCode::from(1));
+        }
+        let page_height = cli
+            .page_height
+            .or_else(|| {
+                terminal_size::terminal_size().map(|(_, h)| h.0 as usize)
+            })
+            .filter(|h| *h > 0);
         let mut read_opts = ReadOptions::default();
+        if let Some(input_format) = cli.input_format {
+            read_opts.format =
+                InputFormat::from_name(&input_format).map_err(|err| {
+                    eprintln!("E: {}", err);
+                    ExitCode::from(1)
+                })?;
+        }
+        if read_opts.format == InputFormat::Csv {
+            read_opts.sep = ",".into();
+        }
         if let Some(field_delimiter) = cli.field_delimiter {
+            if field_delimiter.is_empty() {
+                eprintln!("E: DELIMITER must not be empty");
+                return Err(ExitCode::from(1));
+            }
             read_opts.sep = field_delimiter;
         }
+        if let Some(quote_char) = cli.quote_char {
+            read_opts.quote = quote_char;
+        }
         if cli.enable_escape_sequence {
             read_opts.enable_backslash_escape = true;
         }
+        let color_choice = match cli.color_choice {
+            None => ColorChoice::default(),
+            Some(name) => ColorChoice::from_name(&name).ok_or_else(|| {
+                eprintln!("E: invalid color choice `{}`", name);
+                ExitCode::from(1)
+            })?,
+        };
+        let mut style_opts = StyleOptions::default();
+        if let Some(name) = cli.border_color {
+            style_opts.border_color =
+                Some(parse_color(&name).map_err(|msg| {
+                    eprintln!("E: {}", msg);
+                    ExitCode::from(1)
+                })?);
+        }
+        if let Some(name) = cli.header_style {
+            style_opts.header_style = HeaderStyle::from_name(&name)
+                .ok_or_else(|| {
+                    eprintln!("E: invalid header style `{}`", name);
+                    ExitCode::from(1)
+                })?;
+        }
+        if let Some(name) = cli.zebra_color {
+            style_opts.zebra_color =
+                Some(parse_color(&name).map_err(|msg| {
+                    eprintln!("E: {}", msg);
+                    ExitCode::from(1)
+                })?);
+        }
+        let style_opts =
+            if color_choice.should_style() && !style_opts.is_empty() {
+                Some(style_opts)
+            } else {
+                None
+            };
         Ok(PostCli {
             user_widths,
             user_total_width: cli.user_total_width,
             table_layout,
-            strict: cli.strict,
+            header,
+            header_names,
+            header_break_words,
+            footer,
+            title: cli.title,
+            caption: cli.caption,
+            alignments,
+            vertical_alignments,
+            plan_opts,
+            expanded_mode,
+            column_pages: cli.column_pages,
+            key_columns,
+            max_lines,
+            ellipsis,
+            wrap_algorithm,
+            word_separator,
+            word_splitter,
+            column_wraps,
+            page_height,
+            strict,
             read_opts,
+            style_opts,
             filename: cli.filename,
         })
     }
@@ -118,48 +1003,279 @@ impl Display for crate::Error {
                 write!(f, "The input is not valid utf-8: {}", err)
             }
             crate::Error::Io(err) => write!(f, "IO error occurs: {}.", err),
-            crate::Error::ColumnNotWideEnough(cell) => match cell.as_ref() {
-                None => write!(f, "Some columns are not wide enough."),
-                Some((row_idx, col_idx)) => {
-                    write!(
-                        f,
-                        "Column is not wide enough at row={} column={}.",
-                        row_idx + 1,
-                        col_idx + 1
-                    )
-                }
-            },
+            crate::Error::Json(err) => {
+                write!(f, "The input is not valid JSON: {}", err)
+            }
+            crate::Error::InvalidJsonTable => write!(
+                f,
+                "The JSON input is neither an array of arrays nor an array of objects."
+            ),
+            crate::Error::ColumnNotWideEnough(row_idx, col_idx) => {
+                write!(
+                    f,
+                    "Column is not wide enough at row={} column={}.",
+                    row_idx + 1,
+                    col_idx + 1
+                )
+            }
             crate::Error::TotalWidthNotLargeEnough(total_width) => {
                 write!(f, "Table width {} is not large enough to falicitate the columns and/or table layout.", total_width)
             }
             crate::Error::InvalidTableLayout(name) => {
                 write!(f, "Invalid layout `{}`", name)
             }
+            crate::Error::InvalidInputFormat(name) => {
+                write!(f, "Invalid input format `{}`", name)
+            }
+            crate::Error::UnterminatedCsvQuote(lineno) => {
+                write!(
+                    f,
+                    "Unterminated quoted field starting at line {}.",
+                    lineno
+                )
+            }
+            crate::Error::EmptyCsvSeparator => {
+                write!(f, "The field separator in CSV input is empty.")
+            }
+            crate::Error::TableNotWideEnough(min_total_width, min_widths) => {
+                write!(
+                    f,
+                    "Table width is not large enough, which should be at least {}. The minimum column widths are:",
+                    min_total_width
+                )?;
+                for min_width in min_widths {
+                    write!(
+                        f,
+                        "\n  column {}: {}",
+                        min_width.col_idx + 1,
+                        min_width.width
+                    )?;
+                    match min_width.cause {
+                        MinWidthCause::Row(row_idx) => {
+                            write!(f, " (at row {})", row_idx + 1)?
+                        }
+                        MinWidthCause::WidthRange => {
+                            write!(f, " (by width range)")?
+                        }
+                        MinWidthCause::Unconstrained => {}
+                    }
+                }
+                Ok(())
+            }
+            crate::Error::WidthRangeInfeasible(col_idx, min_width) => {
+                write!(
+                    f,
+                    "The maximum width of column {} is less than {}, the minimum width needed by its content.",
+                    col_idx + 1,
+                    min_width
+                )
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ui::UserWidths;
+    use crate::aggregate::Aggregate;
+    use crate::column_planner::{WidthConstraint, WidthRange};
+    use crate::table::{Alignment, VerticalAlignment};
+    use crate::ui::{
+        parse_word_separator, parse_wrap_algorithm, Cli, UserAlignments,
+        UserColumnWraps, UserFooter, UserKeyColumns, UserMaxLines,
+        UserVerticalAlignments, UserWidths,
+    };
+    use clap::Parser;
+    use textwrap::{WordSeparator, WordSplitter, WrapAlgorithm};
 
     #[test]
     fn test_user_widths() {
         assert_eq!(
             UserWidths::try_from(None).unwrap().into_vec(3),
-            vec![None; 3]
+            vec![WidthConstraint::ANY; 3]
         );
         assert_eq!(
             UserWidths::try_from(Some("4,*,8".into()))
                 .unwrap()
                 .into_vec(3),
-            vec![Some(4), None, Some(8)]
+            vec![
+                WidthConstraint::Fixed(4),
+                WidthConstraint::ANY,
+                WidthConstraint::Fixed(8)
+            ]
         );
         assert_eq!(
             UserWidths::try_from(Some("4,*".into()))
                 .unwrap()
                 .into_vec(3),
-            vec![Some(4), None, None]
+            vec![
+                WidthConstraint::Fixed(4),
+                WidthConstraint::ANY,
+                WidthConstraint::ANY
+            ]
+        );
+        let range = |min, max| WidthConstraint::Range(WidthRange { min, max });
+        assert_eq!(
+            UserWidths::try_from(Some("10..30,..20,15..,..".into()))
+                .unwrap()
+                .into_vec(4),
+            vec![
+                range(10, Some(30)),
+                range(0, Some(20)),
+                range(15, None),
+                range(0, None)
+            ]
+        );
+        assert_eq!(
+            UserWidths::try_from(Some("25%,2fr,1fr".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![
+                WidthConstraint::Percent(25),
+                WidthConstraint::Fraction(2),
+                WidthConstraint::Fraction(1)
+            ]
+        );
+        assert!(UserWidths::try_from(Some("101%".into())).is_err());
+        assert!(UserWidths::try_from(Some("0fr".into())).is_err());
+        assert!(UserWidths::try_from(Some("10001fr".into())).is_err());
+        assert!(
+            UserWidths::try_from(Some("18446744073709551615fr,1fr".into()))
+                .is_err()
+        );
+        assert!(UserWidths::try_from(Some("30..10".into())).is_err());
+        assert!(UserWidths::try_from(Some("a..10".into())).is_err());
+    }
+
+    #[test]
+    fn test_user_alignments() {
+        assert_eq!(
+            UserAlignments::try_from(Some("r,.".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![Alignment::Right, Alignment::Decimal, Alignment::Left]
+        );
+        assert!(UserAlignments::try_from(Some("l,x".into())).is_err());
+    }
+
+    #[test]
+    fn test_user_vertical_alignments() {
+        assert_eq!(
+            UserVerticalAlignments::try_from(Some("m".into()))
+                .unwrap()
+                .into_vec(2),
+            vec![VerticalAlignment::Middle; 2]
+        );
+        assert_eq!(
+            UserVerticalAlignments::try_from(Some("b,m".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![
+                VerticalAlignment::Bottom,
+                VerticalAlignment::Middle,
+                VerticalAlignment::Top
+            ]
+        );
+        assert!(UserVerticalAlignments::try_from(Some("x".into())).is_err());
+    }
+
+    #[test]
+    fn test_user_key_columns() {
+        assert_eq!(
+            UserKeyColumns::try_from(Some("2,1,2,5".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![1, 0]
         );
+        assert!(UserKeyColumns::try_from(Some("0".into())).is_err());
+    }
+
+    #[test]
+    fn test_user_footer() {
+        assert_eq!(
+            UserFooter::try_from(Some("sum:3,count:1,max:5".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![(2, Aggregate::Sum), (0, Aggregate::Count)]
+        );
+        assert!(UserFooter::try_from(Some("sum".into())).is_err());
+        assert!(UserFooter::try_from(Some("median:1".into())).is_err());
+        assert!(UserFooter::try_from(Some("sum:0".into())).is_err());
+        assert!(UserFooter::try_from(Some("sum:1,mean:1".into())).is_err());
+    }
+
+    #[test]
+    fn test_user_max_lines() {
+        assert_eq!(
+            UserMaxLines::try_from(Some("3".into()))
+                .unwrap()
+                .into_vec(2),
+            vec![Some(3); 2]
+        );
+        assert_eq!(
+            UserMaxLines::try_from(Some("*,2".into()))
+                .unwrap()
+                .into_vec(3),
+            vec![None, Some(2), None]
+        );
+        assert!(UserMaxLines::try_from(Some("0".into())).is_err());
+        // `--no-wrap` is a shorthand of `--max-lines 1`.
+        assert!(Cli::try_parse_from([
+            "tabulate",
+            "--no-wrap",
+            "--max-lines",
+            "2"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_user_column_wraps() {
+        let column_wraps =
+            UserColumnWraps::parse(Some("2".into()), Some("n,y".into()), None)
+                .unwrap();
+        let shared = textwrap::Options::new(79);
+        let opts = column_wraps.to_options(&shared, 3);
+        assert_eq!(opts.len(), 3);
+        let opts: Vec<_> = opts.into_iter().map(Option::unwrap).collect();
+        assert_eq!(opts[0].subsequent_indent, "  ");
+        assert_eq!(opts[2].subsequent_indent, "  ");
+        assert!(!opts[0].break_words);
+        assert!(opts[1].break_words);
+        assert!(opts[2].break_words);
+        let column_wraps =
+            UserColumnWraps::parse(None, None, Some("d,n".into())).unwrap();
+        assert!(column_wraps.uses_dictionary());
+        let opts = column_wraps.to_options(&shared, 3);
+        assert!(opts[0].is_some());
+        assert!(matches!(
+            opts[1].as_ref().unwrap().word_splitter,
+            WordSplitter::NoHyphenation
+        ));
+        assert!(opts[2].is_none());
+        assert!(UserColumnWraps::parse(Some("-1".into()), None, None).is_err());
+        assert!(UserColumnWraps::parse(None, Some("x".into()), None).is_err());
+        assert!(UserColumnWraps::parse(None, None, Some("x".into())).is_err());
+    }
+
+    #[test]
+    fn test_parse_wrap_options() {
+        assert!(matches!(
+            parse_wrap_algorithm("first-fit"),
+            Ok(WrapAlgorithm::FirstFit)
+        ));
+        assert!(matches!(
+            parse_wrap_algorithm("optimal-fit"),
+            Ok(WrapAlgorithm::OptimalFit(_))
+        ));
+        assert!(parse_wrap_algorithm("best-fit").is_err());
+        assert!(matches!(
+            parse_word_separator("ascii"),
+            Ok(WordSeparator::AsciiSpace)
+        ));
+        assert!(matches!(
+            parse_word_separator("unicode"),
+            Ok(WordSeparator::UnicodeBreakProperties)
+        ));
+        assert!(parse_word_separator("space").is_err());
     }
 }
@@ -0,0 +1,134 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Run the `tabulate` binary with `args`, feeding `input` to its stdin.
+/// Return the lines of its stdout with trailing whitespace trimmed.
+fn tabulate(args: &[&str], input: &str) -> Vec<String> {
+    let output = run(args, input);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/// Run the `tabulate` binary with `args`, feeding `input` to its stdin.
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tabulate"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+const JSON_OBJECTS: &str = r#"[{"a":1,"b":"x"},{"a":2,"c":[1,2]}]"#;
+
+#[test]
+fn test_json_objects_header() {
+    let lines = tabulate(&["-f", "json", "-T", "20"], JSON_OBJECTS);
+    assert_eq!(
+        lines,
+        [
+            "+---+---+----------+",
+            "| a | b | c        |",
+            "+===+===+==========+",
+            "| 1 | x |          |",
+            "+---+---+----------+",
+            "| 2 |   | [1,2]    |",
+            "+---+---+----------+",
+        ]
+    );
+
+    let lines =
+        tabulate(&["-f", "json", "-T", "20", "-L", "simple"], JSON_OBJECTS);
+    assert_eq!(lines[..2], ["a  b  c", "-  -  --------------"]);
+
+    // Only `--no-header` takes the keys as an ordinary row.
+    let lines = tabulate(
+        &["-f", "json", "-T", "20", "-L", "simple", "--no-header"],
+        JSON_OBJECTS,
+    );
+    assert_eq!(lines[..2], ["-  -  --------------", "a  b  c"]);
+}
+
+#[test]
+fn test_json_objects_header_expanded() {
+    let lines =
+        tabulate(&["-f", "json", "-T", "20", "-x", "on"], JSON_OBJECTS);
+    assert_eq!(
+        lines,
+        [
+            "-[ RECORD 1 ]-------",
+            "a | 1",
+            "b | x",
+            "c |",
+            "-[ RECORD 2 ]-------",
+            "a | 2",
+            "b |",
+            "c | [1,2]",
+        ]
+    );
+}
+
+#[test]
+fn test_markup_alignments() {
+    let input = "a,b\n1,2\n";
+    let output = run(&["-d,", "-L", "html", "-Ar,c", "-Vb"], input);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(
+        "<tr><td style=\"text-align: right; vertical-align: bottom\">1</td>\
+         <td style=\"text-align: center; vertical-align: bottom\">2</td></tr>"
+    ));
+    let output = run(&["-d,", "-L", "latex", "-Ar", "-Vm"], input);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with(
+        "\\begin{tabular}{>{\\raggedleft\\arraybackslash}m{0.5em}m{"
+    ));
+}
+
+#[test]
+fn test_row_pages_separated() {
+    let lines = tabulate(
+        &["-L", "simple", "-T", "4", "--page-height", "5"],
+        "head\nr1\nr2\nr3\n",
+    );
+    assert_eq!(
+        lines,
+        ["head", "----", "r1", "r2", "", "head", "----", "r3"]
+    );
+}
+
+#[test]
+fn test_broken_pipe() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tabulate"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The reader goes away before anything is written.
+    drop(child.stdout.take());
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all("a b\n".repeat(10000).as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}